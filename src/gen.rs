@@ -1,11 +1,18 @@
 use crate::parse;
-use crate::parse::{MacroArgs, SysArgs, SystemArgs};
+use crate::parse::{
+    Arbitrary, CommandGroup, Constructor, DocHidden, ExprExt, GroupItem, MacroArgs, SysArgs,
+    SystemArgs,
+};
 use inflector::*;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{parse_quote, Error, GenericParam, ItemFn, Meta, Signature};
+use syn::{
+    parse_quote, Error, Expr, ExprCall, ExprMethodCall, ExprPath, FnArg, GenericParam, ItemFn,
+    ItemTrait, Meta, MetaNameValue, Pat, Path, ReturnType, Signature, Stmt, TraitItem, TraitItemFn,
+    Type, UseTree,
+};
 
 pub fn commandify(
     args: Punctuated<Meta, syn::Token![,]>,
@@ -26,30 +33,148 @@ pub fn commandify(
         fn_token,
         ident,
         generics,
+        paren_token,
         inputs,
         variadic,
         output,
         ..
     } = sig;
 
+    // generic defaults carry over to our struct, but aren't allowed on fns or impls
+    let struct_generics = generics.clone();
+    let generics = parse::strip_defaults(&generics);
+
     // I actually have no idea if we should care about this case
     if let Some(variadic) = variadic {
         return Err(Error::new(variadic.span(), "command cannot be variadic"));
     }
 
-    // parse return argument
-    let do_return = parse::return_type(&output)?;
-
     // parse macro args
     let MacroArgs {
         no_trait,
         no_world,
+        commands,
+        all,
+        both,
+        spawns,
+        children,
+        descendants,
+        closure,
+        no_struct,
+        example_docs,
+        test,
+        arbitrary,
+        check,
+        check_error,
+        variants,
+        max_per_frame,
+        on_overflow,
+        budget_ms,
+        apply_in_fixed,
+        scoped_to_state,
+        cancel_on_despawn,
+        cached,
+        into_system,
+        no_original,
+        hotkey,
+        sealed,
+        also_impl,
+        read_only,
+        app,
+        run_on_enter,
+        run_on_exit,
+        sub_app,
+        world_impl,
+        constructor,
         name,
+        method,
+        doc_hidden,
         struct_name,
         trait_name,
+        world_trait_name,
+        trait_vis,
+        world_trait_vis,
+        fn_vis,
         ecs_root,
     } = parse::macro_args(&args, ident.clone())?;
 
+    // args which would silently do nothing are warned about, pointing at the arg itself
+    let mut warnings = Vec::<TokenStream>::new();
+    let skip_world = (no_trait && world_trait_name.is_none()) || no_world;
+    if no_trait {
+        if let Some(trait_name) = &trait_name {
+            warnings.push(warning(
+                trait_name.span(),
+                "`trait_name` has no effect with `no_trait`",
+            ));
+        }
+        if let Some(trait_vis) = &trait_vis {
+            warnings.push(warning(
+                trait_vis.span(),
+                "`trait_vis` has no effect with `no_trait`",
+            ));
+        }
+    }
+    if no_world {
+        if let Some(world_trait_name) = &world_trait_name {
+            warnings.push(warning(
+                world_trait_name.span(),
+                "`world_trait_name` has no effect with `no_world`",
+            ));
+        }
+        if let Some(world_trait_vis) = &world_trait_vis {
+            warnings.push(warning(
+                world_trait_vis.span(),
+                "`world_trait_vis` has no effect with `no_world`",
+            ));
+        }
+    }
+    if no_trait && skip_world {
+        if let Some(method) = &method {
+            warnings.push(warning(
+                method.span(),
+                "`method` has no effect, as neither `Commands` nor `World` methods are generated",
+            ));
+        }
+        if example_docs {
+            warnings.push(warning(
+                ident.span(),
+                "`example_docs` has no effect, as neither `Commands` nor `World` methods are generated",
+            ));
+        }
+    }
+
+    // everything we name points back at the original fn, so that navigation and docs resolve to it
+    let respan = |mut name: Ident| {
+        name.set_span(ident.span());
+        name
+    };
+    let name = respan(name);
+    let method = method.map(respan);
+    let struct_name = struct_name.map(respan);
+    let trait_name = trait_name.map(respan);
+    let world_trait_name = world_trait_name.map(respan);
+
+    // parse return argument, read-only commands return whatever they like
+    let do_return = read_only.is_none() && parse::return_type(&output)?;
+
+    // without a struct, the trait is the only way to issue closure commands
+    if let (Some(span), true) = (closure, no_trait) {
+        return Err(Error::new(span, "`closure` commands cannot be `no_trait`"));
+    }
+    if let (Some(span), Some(_)) = (no_struct, closure) {
+        return Err(Error::new(
+            span,
+            "`no_struct` cannot be used with `closure`",
+        ));
+    }
+    if let (Some(arbitrary), true) = (&arbitrary, closure.or(no_struct).is_some()) {
+        return Err(Error::new(
+            arbitrary.span(),
+            "`arbitrary` requires a generated struct, so cannot be used with `closure` or `no_struct`",
+        ));
+    }
+
     // generate default names late so that the `name` field applies
     let command_struct = if entity_command {
         "EntityCommand"
@@ -62,16 +187,54 @@ pub fn commandify(
             name.span(),
         )
     });
+    let trait_prefix = if read_only.is_some() {
+        "World".to_string()
+    } else {
+        format!("{command_struct}s")
+    };
     let trait_name = trait_name.unwrap_or_else(|| {
         Ident::new(
-            &format!("{command_struct}s{}Ext", name.to_string().to_pascal_case()),
+            &format!("{trait_prefix}{}Ext", name.to_string().to_pascal_case()),
             name.span(),
         )
     });
+    let method = method.unwrap_or_else(|| name.clone());
     let ecs_root = ecs_root.unwrap_or_else(|| parse_quote!(::bevy::ecs));
+    let queue = queue_method();
+    let ran = run_once_result();
+    // queueing on state transitions extends the `App` trait, so implies it
+    if let Some(span) = run_on_enter.or(run_on_exit) {
+        if entity_command {
+            return Err(Error::new(
+                span,
+                "`run_on_enter` and `run_on_exit` only apply to commands",
+            ));
+        }
+        if read_only.is_some() {
+            return Err(Error::new(
+                span,
+                "`run_on_enter` and `run_on_exit` cannot be used with `read_only`",
+            ));
+        }
+    }
+    let app = app.or_else(|| {
+        run_on_enter
+            .or(run_on_exit)
+            .map(|_| sibling_crate(&ecs_root, "app"))
+    });
+    let trait_vis = trait_vis.unwrap_or_else(|| parse_quote!(pub));
+    // the world trait is the same as the commands trait, unless asked to be its own
+    let world_trait = world_trait_name
+        .clone()
+        .unwrap_or_else(|| trait_name.clone());
+    let world_trait_vis = world_trait_vis.unwrap_or_else(|| trait_vis.clone());
+    // the fn may be kept private while our generated items share its written visibility
+    let fn_vis = fn_vis.unwrap_or_else(|| vis.clone());
 
     // parse generics
     let mut generic_names = Vec::<TokenStream>::new();
+    // lifetimes may be late-bound, so the turbofish for calling our fn only includes types and consts
+    let mut generic_args = Vec::<TokenStream>::new();
     for param in &generics.params {
         let name = match param {
             GenericParam::Lifetime(inner) => {
@@ -80,35 +243,339 @@ pub fn commandify(
             }
             GenericParam::Type(inner) => {
                 let token = &inner.ident;
+                generic_args.push(quote!(#token));
                 quote!(#token)
             }
             GenericParam::Const(inner) => {
                 let token = &inner.ident;
+                generic_args.push(quote!(#token));
                 quote!(#token)
             }
         };
         generic_names.push(name);
     }
+    let where_clause = &generics.where_clause;
+    let fn_turbofish = if generic_args.is_empty() {
+        quote!()
+    } else {
+        quote!(::< #(#generic_args,)* >)
+    };
 
-    // parse doc comments
+    // parse doc comments, which carry over to our trait methods along with any lints
+    let item_attrs = parse::item_attrs(&attrs);
+    let lints = parse::lint_attrs(&attrs);
     let docs = parse::docs(&attrs);
+    let docs = quote!(#docs #(#lints)*);
+
+    // hide generated machinery from docs, leaving the method docs in place
+    let hidden_struct = if doc_hidden.is_some() {
+        quote!(#[doc(hidden)])
+    } else {
+        quote!()
+    };
+    let hidden_trait = if doc_hidden == Some(DocHidden::All) {
+        quote!(#[doc(hidden)])
+    } else {
+        quote!()
+    };
+
+    // extra types standing in for the world, along with how to reach a mutable and shared world from each
+    let mut world_targets = Vec::<(TokenStream, TokenStream, TokenStream)>::new();
+    if let Some(sub_app) = &sub_app {
+        world_targets.push((
+            quote!(#sub_app ::SubApp),
+            sub_app_world(quote!(self), true),
+            sub_app_world(quote!(self), false),
+        ));
+    }
+    for ty in &world_impl {
+        world_targets.push((
+            quote!(#ty),
+            quote!(::core::convert::AsMut::<#ecs_root ::world::World>::as_mut(self)),
+            quote!(::core::convert::AsRef::<#ecs_root ::world::World>::as_ref(self)),
+        ));
+    }
+    let world_target_types = world_targets.iter().map(|(ty, ..)| ty).collect::<Vec<_>>();
+
+    // seal our traits behind a supertrait which downstream crates cannot name
+    let (sealed_frag, sealed_bound) = if sealed {
+        let module = Ident::new(
+            &format!("__{}_sealed", name.to_string().to_snake_case()),
+            name.span(),
+        );
+        let app_sealed = app
+            .as_ref()
+            .map(|app| quote!(impl #module ::Sealed for #app ::App {}));
+        (
+            quote!(
+                #[doc(hidden)]
+                mod #module {
+                    pub trait Sealed {}
+                    impl Sealed for #ecs_root ::system::Commands<'_, '_> {}
+                    impl Sealed for #ecs_root ::system::EntityCommands<'_> {}
+                    impl Sealed for #ecs_root ::world::World {}
+                    impl Sealed for #ecs_root ::world::EntityWorldMut<'_> {}
+                }
+                #(impl #module ::Sealed for #also_impl {})*
+                #(impl #module ::Sealed for #world_target_types {})*
+                #app_sealed
+            ),
+            quote!(: #module ::Sealed),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
+    // read-only commands are called immediately against the world, so skip the struct and `Command` impl entirely
+    if let Some(span) = read_only {
+        if entity_command {
+            return Err(Error::new(span, "`read_only` only applies to commands"));
+        }
+        if let Some(app) = &app {
+            return Err(Error::new(
+                app.span(),
+                "`app` cannot be used with `read_only`",
+            ));
+        }
+        if let Some(span) = test {
+            return Err(Error::new(span, "`test` cannot be used with `read_only`"));
+        }
+        if let Some(span) = cached {
+            return Err(Error::new(span, "`cached` cannot be used with `read_only`"));
+        }
+        if let Some(span) = no_original {
+            return Err(Error::new(
+                span,
+                "`no_original` cannot be used with `read_only`, as its methods call the fn",
+            ));
+        }
+        if let Some(hotkey) = &hotkey {
+            return Err(Error::new(
+                hotkey.span(),
+                "`hotkey` cannot be used with `read_only`",
+            ));
+        }
+        if let Some(check) = &check {
+            return Err(Error::new(
+                check.span(),
+                "`check` cannot be used with `read_only`",
+            ));
+        }
+        if let Some(constructor) = &constructor {
+            return Err(Error::new(
+                constructor.span(),
+                "`constructor` cannot be used with `read_only`",
+            ));
+        }
+
+        let mut receiver = None;
+        let mut params = Vec::<TokenStream>::new();
+        let mut call_args = Vec::<TokenStream>::new();
+        let mut forward_args = Vec::<TokenStream>::new();
+        let mut param_docs = Vec::<Option<String>>::new();
+        for (index, input) in inputs.iter().enumerate() {
+            let FnArg::Typed(pt) = input else {
+                return Err(Error::new(input.span(), "Commands cannot be methods"));
+            };
+            match pt.ty.as_ref() {
+                Type::Reference(tr) if tr.mutability.is_none() && parse::is_world(&tr.elem) => {
+                    // an explicit lifetime on the world carries over, so the return value may borrow from it
+                    let lifetime = &tr.lifetime;
+                    receiver = Some(quote!(&#lifetime self));
+                    call_args.push(quote!(self));
+                }
+                ty => {
+                    let param = match pt.pat.as_ref() {
+                        Pat::Ident(pat) => pat.ident.clone(),
+                        _ => Ident::new(&format!("__arg{index}"), Span::call_site()),
+                    };
+                    params.push(quote!(#param: #ty));
+                    call_args.push(quote!(#param));
+                    forward_args.push(quote!(#param));
+                    param_docs.push(parse::param_docs(&pt.attrs)?);
+                }
+            }
+        }
+        let Some(receiver) = receiver else {
+            return Err(Error::new(
+                paren_token.span.join(),
+                "Read-only commands must take in a `&World` parameter",
+            ));
+        };
+
+        let arguments_doc = arguments_doc(forward_args.iter().zip(&param_docs));
+        let example_doc = if example_docs && !no_trait {
+            let args = forward_args
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            example_doc(&[format!("let value = world.{method}({args});")])
+        } else {
+            quote!()
+        };
+        let docs = quote!(#docs #arguments_doc #example_doc);
+
+        // the result is the whole point of calling a read-only command, keeping any message from the fn
+        let must_use = match (
+            &output,
+            attrs.iter().find(|attr| attr.path().is_ident("must_use")),
+        ) {
+            (_, Some(attr)) => quote!(#attr),
+            (ReturnType::Type(..), None) => quote!(#[must_use]),
+            _ => quote!(),
+        };
+
+        let also_impls = also_impl.iter().map(|ty| {
+            quote!(
+                impl #trait_name for #ty {
+                    fn #method #generics (#receiver #(, #params)*) #output #where_clause {
+                        #trait_name :: #method #fn_turbofish (::core::ops::Deref::deref(self) #(, #forward_args)*)
+                    }
+                }
+            )
+        });
+        let world_target_impls = world_targets.iter().map(|(ty, _, world)| {
+            quote!(
+                impl #trait_name for #ty {
+                    fn #method #generics (#receiver #(, #params)*) #output #where_clause {
+                        #trait_name :: #method #fn_turbofish (#world #(, #forward_args)*)
+                    }
+                }
+            )
+        });
+        let trait_frag = if no_trait {
+            quote!()
+        } else {
+            quote!(
+                #hidden_trait
+                #trait_vis trait #trait_name #sealed_bound {
+                    #docs
+                    #must_use
+                    fn #method #generics (#receiver #(, #params)*) #output #where_clause;
+                }
+
+                impl #trait_name for #ecs_root ::world::World {
+                    fn #method #generics (#receiver #(, #params)*) #output #where_clause {
+                        #ident #fn_turbofish (#(#call_args),*)
+                    }
+                }
+
+                #(#also_impls)*
+                #(#world_target_impls)*
+            )
+        };
+
+        let inputs = parse::strip_helper_attrs(&inputs);
+        return Ok(quote!(
+            #(#attrs)*
+            #fn_vis #constness #asyncness #unsafety #abi #fn_token #ident #generics (#inputs) #output #where_clause
+            #block
+
+            #trait_frag
+            #sealed_frag
+            #(#warnings)*
+        ));
+    }
 
     // parse fn args
     let SysArgs {
         entity,
+        target,
+        optional_target,
         fields,
+        field_docs,
+        field_attrs,
         def_field_names,
         impl_field_names,
+        field_pats,
         args,
-    } = parse::fn_args(&inputs, entity_command)?;
+    } = parse::fn_args(&inputs, entity_command, &ecs_root)?;
+    let input_type = system_input(parse::input_type(&inputs), &ecs_root);
+    let inputs = parse::strip_helper_attrs(&inputs);
+
+    // param docs carry over to our struct fields along with any other attributes, and are listed in our method docs
+    let field_doc_attrs = field_docs
+        .iter()
+        .zip(&field_attrs)
+        .map(|(doc, attrs)| {
+            let doc = doc.as_ref().map(|doc| quote!(#[doc = #doc]));
+            quote!(#doc #(#attrs)*)
+        })
+        .collect::<Vec<_>>();
+    let arguments_doc = arguments_doc(def_field_names.iter().zip(&field_docs));
+    let example_doc = if example_docs {
+        let args = def_field_names
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut lines = Vec::new();
+        let (commands, world) = if entity_command {
+            ("commands.entity(entity)", "world.entity_mut(entity)")
+        } else {
+            ("commands", "world")
+        };
+        if !no_trait {
+            lines.push(format!("{commands}.{method}({args});"));
+        }
+        if !skip_world {
+            lines.push(format!("{world}.{method}({args});"));
+        }
+        example_doc(&lines)
+    } else {
+        quote!()
+    };
+    let docs = quote!(#docs #arguments_doc #example_doc);
 
     if entity_command && entity.is_none() {
         return Err(Error::new(
-            Span::call_site(),
+            paren_token.span.join(),
             "Entity commands must take in a `Entity` parameter",
         ));
     }
 
+    // names of the extra items generated by `commands`, `all`, `both`, `spawns`, and `Option<Entity>` targets
+    let pascal_name = name.to_string().to_pascal_case();
+    let commands_trait_name = Ident::new(&format!("Commands{pascal_name}Ext"), name.span());
+    let plain_name = Ident::new(&format!("{pascal_name}Command"), name.span());
+    let entity_trait_name = Ident::new(&format!("EntityCommands{pascal_name}Ext"), name.span());
+    let app_trait_name = Ident::new(&format!("App{pascal_name}Ext"), name.span());
+
+    // catch our own items colliding, rather than leaving it to confusing duplicate definition errors
+    let mut generated = Vec::<(&Ident, &str, &str)>::new();
+    if closure.is_none() && no_struct.is_none() {
+        generated.push((&struct_name, "struct", "struct_name"));
+    }
+    if !no_trait {
+        generated.push((&trait_name, "trait", "trait_name"));
+    }
+    if let (Some(world_trait_name), false) = (&world_trait_name, no_world) {
+        generated.push((world_trait_name, "world trait", "world_trait_name"));
+    }
+    if commands.or(all).or(both).or(spawns).is_some() {
+        generated.push((&commands_trait_name, "`commands` trait", "name"));
+    }
+    if both.is_some() {
+        generated.push((&plain_name, "`both` struct", "name"));
+    }
+    if optional_target.is_some() && !no_trait && closure.is_none() {
+        generated.push((&entity_trait_name, "entity trait", "name"));
+    }
+    if app.is_some() {
+        generated.push((&app_trait_name, "`app` trait", "name"));
+    }
+    for (index, (ident, item, arg)) in generated.iter().enumerate() {
+        if let Some((_, other_item, other_arg)) =
+            generated[..index].iter().find(|(other, ..)| other == ident)
+        {
+            return Err(Error::new(
+                ident.span(),
+                format!("`{ident}` would name both the {other_item} and the {item}, rename one with `{other_arg}` or `{arg}`"),
+            ));
+        }
+    }
+
     // generate fragments to be combined later
 
     let generic_names = if generic_names.is_empty() {
@@ -116,12 +583,35 @@ pub fn commandify(
     } else {
         quote!(< #(#generic_names,)* >)
     };
+    let struct_turbofish = if generic_names.is_empty() {
+        quote!()
+    } else {
+        quote!(:: #generic_names)
+    };
+
+    // type params only used by system params, eg. `StaticSystemParam<P>`, would otherwise go unused by our struct
+    let marker_params = parse::unused_type_params(&generics, &fields);
+    let (marker_field, marker_def, marker_rest) = if marker_params.is_empty() {
+        (quote!(), quote!(), quote!())
+    } else {
+        (
+            quote!(pub _marker: ::core::marker::PhantomData<fn() -> (#(#marker_params,)*)>,),
+            quote!(_marker: ::core::marker::PhantomData,),
+            quote!(..),
+        )
+    };
+
+    // exclusive bodies are inlined into our command, leaving the original fn unused
+    let fn_allow = match &args {
+        SystemArgs::Exclusive { .. } => quote!(#[allow(dead_code)]),
+        SystemArgs::System { .. } => quote!(),
+    };
 
     // piece back the original system sans return type
     let fn_frag = quote!(
-        #[allow(unused)]
+        #fn_allow
         #(#attrs)*
-        #vis
+        #fn_vis
         #constness
         #asyncness
         #unsafety
@@ -131,23 +621,27 @@ pub fn commandify(
         #generics
         (#inputs)
         #variadic
+        #where_clause
         #block
     );
 
     // which trait we're implementing for
+    let command_module = command_module();
+    let command_queue_module = command_queue_module();
+    let resource_trait = resource_trait(&ecs_root);
     let command_trait = if entity_command {
-        quote!(EntityCommand)
+        quote!(system::EntityCommand)
     } else {
-        quote!(Command)
+        quote!(#command_module ::Command)
     };
 
     let return_frag = if do_return { quote!(self) } else { quote!() };
 
     // the fields of our generated struct
-    let struct_fields_frag = if fields.is_empty() {
-        quote!( ; )
+    let struct_fields_frag = if fields.is_empty() && marker_params.is_empty() {
+        quote!( #where_clause ; )
     } else {
-        quote!( { #(pub #fields,)* } )
+        quote!( #where_clause { #(#field_doc_attrs pub #fields,)* #marker_field } )
     };
 
     // The inputs passed to our system
@@ -164,136 +658,1021 @@ pub fn commandify(
         }
     };
 
-    // Generates a `Commands` or `EntityCommands` impl for our struct
-    let impl_command_frag = match &args {
-        SystemArgs::Exclusive { world } => {
-            let apply_params = if entity_command {
-                quote!((self, #entity, #world))
+    let run_with = run_once_with(&system_in_frag, quote!(#ident #fn_turbofish));
+
+    // scope a `Commands` to exclusive bodies, applying anything queued once it's done
+    let body = match &args {
+        SystemArgs::Exclusive {
+            world_name,
+            commands: Some(pat),
+            ..
+        } => quote!(
+            let mut __queue = #ecs_root :: #command_queue_module ::CommandQueue::default();
+            {
+                let #pat = #ecs_root ::system::Commands::new(&mut __queue, #world_name);
+                #block
+            }
+            __queue.apply(#world_name);
+        ),
+        _ => quote!(#block),
+    };
+
+    // closure commands queue their body directly rather than a named struct
+    let closure_frag = match &args {
+        _ if closure.is_none() => quote!(),
+        SystemArgs::Exclusive {
+            world,
+            fetch,
+            params,
+            ..
+        } => {
+            if let Some(param) = params.first() {
+                return Err(Error::new(
+                    param.span(),
+                    "`closure` commands cannot take exclusive system params",
+                ));
+            }
+            let (closure_params, bind_params) = if entity_command {
+                entity_command_params(&entity, world, &ecs_root)
+            } else {
+                (quote!(#world), quote!())
+            };
+            quote!(
+                move |#closure_params| {
+                    #bind_params
+                    #(let #impl_field_names = #def_field_names;)*
+                    #fetch
+                    #body
+                }
+            )
+        }
+        SystemArgs::System { systems_in, .. } => {
+            let (closure_params, bind_params) = if entity_command {
+                entity_command_params(
+                    &entity,
+                    quote!(world: &mut #ecs_root ::world::World),
+                    &ecs_root,
+                )
+            } else {
+                (quote!(world: &mut #ecs_root ::world::World), quote!())
+            };
+            let run_frag = if systems_in.is_empty() {
+                quote!(world.run_system_once(#ident #fn_turbofish)#ran;)
             } else {
-                quote!((self, #world))
+                quote!(world.#run_with #ran;)
             };
+            quote!(
+                move |#closure_params| {
+                    use #ecs_root ::system::RunSystemOnce;
+                    #bind_params
+                    #run_frag
+                }
+            )
+        }
+    };
 
+    // what our methods queue or apply, either the struct or the closure
+    let budget_name = Ident::new(&format!("__{struct_name}Budget"), struct_name.span());
+    let drain_name = Ident::new(
+        &format!("{}_drain", struct_name.to_string().to_snake_case()),
+        ident.span(),
+    );
+    let buffered = budget_ms.as_ref().map(Spanned::span).or(apply_in_fixed);
+    let (queued_frag, apply_frag) = if closure.is_some() {
+        (closure_frag, quote!(#ecs_root :: #command_trait ::apply))
+    } else if buffered.is_some() {
+        // budgeted and fixed commands are buffered, to be applied by our drain system
+        let command = quote!(#struct_name #struct_turbofish {#(#def_field_names,)* #marker_def});
+        let queued = if entity_command {
+            let (params, bind_params) = entity_command_params(
+                quote!(entity: #ecs_root ::entity::Entity),
+                quote!(world: &mut #ecs_root ::world::World),
+                &ecs_root,
+            );
             quote!(
-                impl #generics #ecs_root ::system:: #command_trait for #struct_name #generic_names {
-                    fn apply #apply_params {
-                        let #struct_name {#(#impl_field_names,)*} = self;
-                        #block
-                    }
+                move |#params| {
+                    #bind_params
+                    world.get_resource_or_insert_with(#budget_name::default).pending.push_back((entity, #command));
+                }
+            )
+        } else {
+            quote!(
+                move |world: &mut #ecs_root ::world::World| {
+                    world.get_resource_or_insert_with(#budget_name::default).pending.push_back(#command);
                 }
             )
+        };
+        (queued, quote!(#ecs_root :: #command_trait ::apply))
+    } else {
+        (
+            quote!(#struct_name #struct_turbofish {#(#def_field_names,)* #marker_def}),
+            quote!(<#struct_name #generic_names as #ecs_root :: #command_trait>::apply),
+        )
+    };
+
+    // what our other trait methods apply to each target, buffering our struct like the main method does
+    let method_apply = match (buffered, entity_command) {
+        (Some(_), true) if closure.is_none() => {
+            let (params, bind_params) = entity_command_params(
+                quote!(entity: #ecs_root ::entity::Entity),
+                quote!(world: &mut #ecs_root ::world::World),
+                &ecs_root,
+            );
+            quote!(
+                (|command: #struct_name, #params| {
+                    #bind_params
+                    world.get_resource_or_insert_with(#budget_name::default).pending.push_back((entity, command));
+                })
+            )
         }
-        SystemArgs::System { .. } => {
-            let apply_params = if entity_command {
-                quote!((self, #entity, world: &mut #ecs_root ::world::World))
+        (Some(_), false) if closure.is_none() => quote!(
+            (|command: #struct_name, world: &mut #ecs_root ::world::World| {
+                world.get_resource_or_insert_with(#budget_name::default).pending.push_back(command);
+            })
+        ),
+        _ => quote!(<#struct_name #generic_names as #ecs_root :: #command_trait>::apply),
+    };
+
+    // applies buffered commands until the budget for this run is spent, leaving the rest for the next
+    let budget_frag = match buffered {
+        None => quote!(),
+        Some(span) if closure.is_some() => {
+            return Err(Error::new(
+                span,
+                "`budget_ms` and `apply_in_fixed` cannot be used with `closure`",
+            ))
+        }
+        Some(span) if !generics.params.is_empty() => {
+            return Err(Error::new(
+                span,
+                "`budget_ms` and `apply_in_fixed` cannot be used with generic commands",
+            ))
+        }
+        Some(_) => {
+            let pending = if entity_command {
+                quote!((#ecs_root ::entity::Entity, #struct_name))
             } else {
-                quote!((self, world: &mut #ecs_root ::world::World))
+                quote!(#struct_name)
+            };
+            let apply = match cancel_on_despawn {
+                // `no_struct` commands have no `apply_batch`, so are applied one at a time
+                None if no_struct.is_some() => {
+                    let apply = if entity_command {
+                        let apply = apply_entity_command(
+                            quote!(<#struct_name as #ecs_root :: #command_trait>::apply),
+                            quote!(command),
+                            quote!(entity),
+                            quote!(world),
+                        );
+                        quote!(
+                            let Some((entity, command)) = pending.pop_front() else {
+                                break;
+                            };
+                            #apply;
+                        )
+                    } else {
+                        quote!(
+                            let Some(command) = pending.pop_front() else {
+                                break;
+                            };
+                            <#struct_name as #ecs_root :: #command_trait>::apply(command, world);
+                        )
+                    };
+                    quote!(
+                        while start.elapsed() < budget {
+                            #apply
+                        }
+                    )
+                }
+                None => quote!(
+                    #struct_name::apply_batch(
+                        ::core::iter::from_fn(|| {
+                            if start.elapsed() < budget {
+                                pending.pop_front()
+                            } else {
+                                None
+                            }
+                        }),
+                        world,
+                    );
+                ),
+                Some(span) if !entity_command => {
+                    return Err(Error::new(
+                        span,
+                        "`cancel_on_despawn` only applies to entity commands",
+                    ))
+                }
+                // targets may despawn while their command is buffered, or during this very drain, so check each in turn
+                Some(_) => {
+                    let apply = apply_entity_command(
+                        quote!(<#struct_name as #ecs_root :: #command_trait>::apply),
+                        quote!(command),
+                        quote!(entity),
+                        quote!(world),
+                    );
+                    quote!(
+                        while start.elapsed() < budget {
+                            let Some((entity, command)) = pending.pop_front() else {
+                                break;
+                            };
+                            if world.entities().contains(entity) {
+                                #apply;
+                            }
+                        }
+                    )
+                }
             };
-            if fields.is_empty() {
+            let (duration, instant) = time_types(&ecs_root);
+            // without a budget, everything pending is applied in one go
+            let (budget, doc) = match &budget_ms {
+                Some(budget) => (
+                    quote!(#duration::from_secs_f64(#budget as f64 / 1000.0)),
+                    format!(
+                        " Applies pending `{struct_name}`s until {}ms have passed, leaving the rest for the next run",
+                        budget.to_token_stream()
+                    ),
+                ),
+                None => (
+                    quote!(#duration::MAX),
+                    format!(" Applies all pending `{struct_name}`s"),
+                ),
+            };
+
+            // drains our buffer at the start of each fixed timestep, rather than leaving it to the caller
+            let fixed_frag = if apply_in_fixed.is_some() {
+                let plugin_name = Ident::new(
+                    &format!("{}FixedPlugin", name.to_string().to_pascal_case()),
+                    name.span(),
+                );
+                let app = app
+                    .clone()
+                    .unwrap_or_else(|| sibling_crate(&ecs_root, "app"));
+                let doc =
+                    format!(" Applies pending `{struct_name}`s at the start of each `FixedUpdate`");
                 quote!(
-                    impl #generics #ecs_root ::system:: #command_trait for #struct_name #generic_names {
-                        fn apply #apply_params {
-                            use #ecs_root ::system::RunSystemOnce;
-                            world.run_system_once(#ident);
+                    #[doc = #doc]
+                    #vis struct #plugin_name;
+
+                    impl #app ::Plugin for #plugin_name {
+                        fn build(&self, app: &mut #app ::App) {
+                            app.add_systems(#app ::FixedFirst, #drain_name);
                         }
                     }
                 )
             } else {
+                quote!()
+            };
+
+            quote!(
+                #[doc(hidden)]
+                #[derive(Default)]
+                struct #budget_name {
+                    pending: ::std::collections::VecDeque<#pending>,
+                }
+
+                impl #resource_trait for #budget_name {}
+
+                #[doc = #doc]
+                #vis fn #drain_name(world: &mut #ecs_root ::world::World) {
+                    let Some(mut buffer) = world.get_resource_mut::<#budget_name>() else {
+                        return;
+                    };
+                    let mut pending = ::core::mem::take(&mut buffer.pending);
+                    let start = #instant::now();
+                    let budget = #budget;
+                    #apply
+                    // anything queued while draining goes after what we left over
+                    let mut buffer = world.get_resource_or_insert_with(#budget_name::default);
+                    pending.append(&mut buffer.pending);
+                    buffer.pending = pending;
+                }
+
+                #fixed_frag
+            )
+        }
+    };
+
+    if let (Some(span), None) = (cancel_on_despawn, buffered) {
+        return Err(Error::new(
+            span,
+            "`cancel_on_despawn` requires `budget_ms` or `apply_in_fixed`, which buffer our command",
+        ));
+    }
+
+    // drops our buffered commands once the state they belong to exits
+    let scope_frag = match (&scoped_to_state, buffered) {
+        (None, _) => quote!(),
+        (Some(state), None) => return Err(Error::new(
+            state.span(),
+            "`scoped_to_state` requires `budget_ms` or `apply_in_fixed`, which buffer our command",
+        )),
+        (Some(state), Some(_)) => {
+            let plugin_name = Ident::new(
+                &format!("{}StateScopePlugin", name.to_string().to_pascal_case()),
+                name.span(),
+            );
+            let app = app
+                .clone()
+                .unwrap_or_else(|| sibling_crate(&ecs_root, "app"));
+            let state_module = state_module(&ecs_root);
+            let doc = format!(
+                " Drops any pending `{struct_name}`s when exiting `{}`",
+                state.to_token_stream().to_string().replace(' ', "")
+            );
+            quote!(
+                #[doc = #doc]
+                #vis struct #plugin_name;
+
+                impl #app ::Plugin for #plugin_name {
+                    fn build(&self, app: &mut #app ::App) {
+                        app.add_systems(#state_module ::OnExit(#state), |world: &mut #ecs_root ::world::World| {
+                            if let Some(mut buffer) = world.get_resource_mut::<#budget_name>() {
+                                buffer.pending.clear();
+                            }
+                        });
+                    }
+                }
+            )
+        }
+    };
+
+    // skips applications beyond the limit for this frame, counted by a resource of our own
+    let limit_name = Ident::new(&format!("__{struct_name}Limit"), struct_name.span());
+    let (limit_frag, limit_check) = match (&max_per_frame, &on_overflow) {
+        (None, Some(on_overflow)) => {
+            return Err(Error::new(
+                on_overflow.span(),
+                "`on_overflow` requires `max_per_frame`",
+            ))
+        }
+        (None, None) => (quote!(), quote!()),
+        (Some(max), _) if closure.is_some() => {
+            return Err(Error::new(
+                max.span(),
+                "`max_per_frame` cannot be used with `closure`",
+            ))
+        }
+        (Some(max), on_overflow) => {
+            let world = match &args {
+                SystemArgs::Exclusive { world_name, .. } => world_name.clone(),
+                SystemArgs::System { .. } => quote!(world),
+            };
+            let overflow = match (on_overflow, &target) {
+                (None, _) => quote!(),
+                (Some(on_overflow), _) if !entity_command => quote!(#on_overflow (self, #world);),
+                (Some(on_overflow), Some(target)) => quote!(#on_overflow (self, #target, #world);),
+                (Some(on_overflow), None) => {
+                    return Err(Error::new(
+                        on_overflow.span(),
+                        "`on_overflow` requires the target entity to be a plain name",
+                    ))
+                }
+            };
+            let frame_count = frame_count(&ecs_root);
+            (
                 quote!(
-                    impl #generics #ecs_root ::system:: #command_trait for #struct_name #generic_names {
-                        fn apply #apply_params {
-                            use #ecs_root ::system::RunSystemOnce;
-                            let #struct_name {#(#def_field_names,)*} = self;
-                            world.run_system_once_with(#system_in_frag, #ident);
+                    #[doc(hidden)]
+                    #[derive(Default)]
+                    struct #limit_name {
+                        frame: u32,
+                        count: usize,
+                    }
+
+                    impl #resource_trait for #limit_name {}
+
+                    impl #limit_name {
+                        /// counts an application, returning whether it's within this frame's limit
+                        fn allow(world: &mut #ecs_root ::world::World) -> bool {
+                            // without frames to count, there's no frame to limit applications to
+                            let Some(frame) = world.get_resource::<#frame_count>().map(|frame| frame.0) else {
+                                return true;
+                            };
+                            let mut limit = world.get_resource_or_insert_with(Self::default);
+                            if limit.frame != frame {
+                                limit.frame = frame;
+                                limit.count = 0;
+                            }
+                            limit.count += 1;
+                            limit.count <= #max
                         }
                     }
-                )
+                ),
+                quote!(
+                    if !#limit_name::allow(#world) {
+                        #overflow
+                        return;
+                    }
+                ),
+            )
+        }
+    };
+
+    // keeps our system initialized between applications, rather than rebuilding it through `run_system_once`
+    let cache_name = Ident::new(&format!("__{struct_name}System"), struct_name.span());
+    let cache_frag = match (&cached, &args) {
+        (None, _) => quote!(),
+        (Some(span), _) if closure.is_some() || no_struct.is_some() => return Err(Error::new(
+            *span,
+            "`cached` requires a generated struct, so cannot be used with `closure` or `no_struct`",
+        )),
+        (Some(span), _) if generics.lifetimes().next().is_some() => {
+            return Err(Error::new(
+                *span,
+                "`cached` cannot be used with lifetime params, as resources must be `'static`",
+            ))
+        }
+        (Some(span), SystemArgs::Exclusive { .. }) => return Err(Error::new(
+            *span,
+            "`cached` only applies to system commands, exclusive commands have no system to keep",
+        )),
+        // generic commands keep a resource per instantiation, each registered on first use
+        (Some(_), SystemArgs::System { .. }) => {
+            let type_params = generics
+                .type_params()
+                .map(|param| &param.ident)
+                .collect::<Vec<_>>();
+            let mut static_bounds = where_clause.clone().unwrap_or_else(|| parse_quote!(where));
+            for param in &type_params {
+                static_bounds.predicates.push(parse_quote!(#param: 'static));
             }
+            quote!(
+                #[doc(hidden)]
+                struct #cache_name #generics (
+                    ::core::option::Option<::std::boxed::Box<dyn #ecs_root ::system::System<In = #input_type, Out = ()>>>,
+                    ::core::marker::PhantomData<fn() -> (#(#type_params,)*)>,
+                ) #where_clause;
+
+                impl #generics #resource_trait for #cache_name #generic_names #static_bounds {}
+            )
         }
     };
 
-    // Generates a new trait + method for issuing our command
-    // Implements this new trait for `Commands` or `EntityCommands`
-    let commands_trait_frag = match &args {
-        SystemArgs::Exclusive { .. } => {
-            if no_trait {
-                quote!()
+    // Generates a `Commands` or `EntityCommands` impl for our struct
+    let impl_command_frag = match &args {
+        _ if closure.is_some() => quote!(),
+        SystemArgs::Exclusive {
+            world,
+            world_name,
+            fetch,
+            params,
+            ..
+        } => {
+            let (apply_params, bind_params) = if entity_command {
+                entity_command_params(&entity, world, &ecs_root)
             } else {
-                let commands_struct = if entity_command {
-                    quote!(EntityCommands<'_>)
-                } else {
-                    quote!(Commands<'_, '_>)
-                };
+                (world.clone(), quote!())
+            };
+
+            if params.is_empty() {
                 quote!(
-                    pub trait #trait_name {
-                        #docs
-                        fn #name #generics (&mut self, #(#fields,)*) #output;
+                    impl #generics #ecs_root :: #command_trait for #struct_name #generic_names #where_clause {
+                        #(#lints)*
+                        fn apply(self, #apply_params) {
+                            #bind_params
+                            #limit_check
+                            // unused fields are already reported on the original fn's params
+                            #[allow(unused_variables)]
+                            let #struct_name {#(#field_pats,)* #marker_rest} = self;
+                            #fetch
+                            #body
+                        }
                     }
-
-                    impl #trait_name for #ecs_root ::system:: #commands_struct {
-                        fn #name #generics (&mut self, #(#fields,)*) #output {
-                            self.add(#struct_name {#(#def_field_names,)*});
-                            #return_frag
+                )
+            } else {
+                // exclusive system params need to be initialized, so run our body as an exclusive system taking the command as input
+                let run = run_once_with(
+                    quote!(self),
+                    quote!(move |#ecs_root ::system::In(__command): #ecs_root ::system::In<Self>, #world #(, #params)*| {
+                        // unused fields are already reported on the original fn's params
+                        #[allow(unused_variables)]
+                        let #struct_name {#(#field_pats,)* #marker_rest} = __command;
+                        #fetch
+                        #body
+                    }),
+                );
+                quote!(
+                    impl #generics #ecs_root :: #command_trait for #struct_name #generic_names #where_clause {
+                        #(#lints)*
+                        fn apply(self, #apply_params) {
+                            use #ecs_root ::system::RunSystemOnce;
+                            #bind_params
+                            #limit_check
+                            #world_name.#run #ran;
                         }
                     }
                 )
             }
         }
-        SystemArgs::System { .. } => {
-            if no_trait {
-                quote!()
+        SystemArgs::System { systems_in, .. } => {
+            let (apply_params, bind_params) = if entity_command {
+                entity_command_params(
+                    &entity,
+                    quote!(world: &mut #ecs_root ::world::World),
+                    &ecs_root,
+                )
             } else {
-                let commands_struct = if entity_command {
-                    quote!(EntityCommands<'_>)
+                (quote!(world: &mut #ecs_root ::world::World), quote!())
+            };
+            if cached.is_some() {
+                let run_in = if systems_in.is_empty() {
+                    quote!(())
                 } else {
-                    quote!(Commands<'_, '_>)
+                    system_in_frag.clone()
                 };
-
                 quote!(
-                    pub trait #trait_name {
-                        #docs
-                        fn #name #generics (&mut self #(,#fields,)*) #output;
+                    impl #generics #ecs_root :: #command_trait for #struct_name #generic_names #where_clause {
+                        fn apply(self, #apply_params) {
+                            use #ecs_root ::system::System;
+                            #bind_params
+                            #limit_check
+                            let #struct_name {#(#def_field_names,)* #marker_rest} = self;
+                            // the system is taken while it runs, so nested applications of our command build their own
+                            let mut system = world
+                                .get_resource_mut::<#cache_name #generic_names>()
+                                .and_then(|mut cache| cache.0.take())
+                                .unwrap_or_else(|| {
+                                    let mut system: ::std::boxed::Box<dyn #ecs_root ::system::System<In = #input_type, Out = ()>> =
+                                        ::std::boxed::Box::new(#ecs_root ::system::IntoSystem::into_system(#ident #fn_turbofish));
+                                    system.initialize(world);
+                                    system
+                                });
+                            system.run(#run_in, world);
+                            system.apply_deferred(world);
+                            world.insert_resource(#cache_name #struct_turbofish (
+                                ::core::option::Option::Some(system),
+                                ::core::marker::PhantomData,
+                            ));
+                        }
                     }
-
-                    impl #trait_name for #ecs_root ::system:: #commands_struct {
-                        fn #name #generics (&mut self #(,#fields,)*) #output {
-                            self.add(#struct_name {#(#def_field_names,)*});
-                            #return_frag
+                )
+            } else if systems_in.is_empty() {
+                quote!(
+                    impl #generics #ecs_root :: #command_trait for #struct_name #generic_names #where_clause {
+                        fn apply(self, #apply_params) {
+                            use #ecs_root ::system::RunSystemOnce;
+                            #bind_params
+                            #limit_check
+                            world.run_system_once(#ident #fn_turbofish)#ran;
                         }
                     }
                 )
-            }
+            } else {
+                quote!(
+                    impl #generics #ecs_root :: #command_trait for #struct_name #generic_names #where_clause {
+                        fn apply(self, #apply_params) {
+                            use #ecs_root ::system::RunSystemOnce;
+                            #bind_params
+                            #limit_check
+                            let #struct_name {#(#def_field_names,)* #marker_rest} = self;
+                            world.#run_with #ran;
+                        }
+                    }
+                )
+            }
         }
     };
 
-    // Implements the same trait as above, but for `World` or `EntityWorldMut`
-    let impl_world_frag = match &args {
+    // Applies many of our struct at once, initializing the system behind it only once
+    let batch_frag = if closure.is_some() || no_struct.is_some() {
+        quote!()
+    } else {
+        let (item, each) = if entity_command {
+            (
+                quote!((#ecs_root ::entity::Entity, Self)),
+                quote!(for (__entity, __command) in commands),
+            )
+        } else {
+            (quote!(Self), quote!(for __command in commands))
+        };
+        let entity_frag = if entity_command {
+            quote!(let #entity = __entity;)
+        } else {
+            quote!()
+        };
+        let apply = if entity_command {
+            let apply = apply_entity_command(
+                quote!(<Self as #ecs_root :: #command_trait>::apply),
+                quote!(__command),
+                quote!(__entity),
+                quote!(world),
+            );
+            quote!(#apply;)
+        } else {
+            quote!(<Self as #ecs_root :: #command_trait>::apply(__command, world);)
+        };
+        let body = match &args {
+            // limited commands are counted one at a time, and plain exclusive commands have nothing to initialize
+            SystemArgs::Exclusive { params, .. } if params.is_empty() => quote!(#each { #apply }),
+            _ if max_per_frame.is_some() => quote!(#each { #apply }),
+            SystemArgs::Exclusive {
+                world,
+                fetch,
+                params,
+                ..
+            } => {
+                let input = if entity_command {
+                    quote!((__entity, __command))
+                } else {
+                    quote!(__command)
+                };
+                quote!(
+                    use #ecs_root ::system::System;
+                    let mut system = #ecs_root ::system::IntoSystem::into_system(
+                        move |#ecs_root ::system::In(#input): #ecs_root ::system::In<#item>, #world #(, #params)*| {
+                            #entity_frag
+                            // unused fields are already reported on the original fn's params
+                            #[allow(unused_variables)]
+                            let #struct_name {#(#field_pats,)* #marker_rest} = __command;
+                            #fetch
+                            #body
+                        },
+                    );
+                    system.initialize(world);
+                    #each {
+                        system.run(#input, world);
+                        system.apply_deferred(world);
+                    }
+                )
+            }
+            SystemArgs::System { systems_in, .. } => {
+                let run_in = if systems_in.is_empty() {
+                    quote!(())
+                } else {
+                    system_in_frag.clone()
+                };
+                quote!(
+                use #ecs_root ::system::System;
+                let mut system = #ecs_root ::system::IntoSystem::into_system(#ident #fn_turbofish);
+                system.initialize(world);
+                #each {
+                    #entity_frag
+                    let #struct_name {#(#def_field_names,)* #marker_rest} = __command;
+                    system.run(#run_in, world);
+                    system.apply_deferred(world);
+                }
+                )
+            }
+        };
+        quote!(
+            impl #generics #struct_name #generic_names #where_clause {
+                /// Applies each of `commands` in turn, initializing any system params once for the whole batch
+                ///
+                /// `Local`s therefore carry over from one command to the next
+                #[allow(unused)]
+                #(#lints)*
+                #vis fn apply_batch(commands: impl ::core::iter::IntoIterator<Item = #item>, world: &mut #ecs_root ::world::World) {
+                    #body
+                }
+            }
+        )
+    };
+
+    // the components and resources our command reads and writes, so conflicts between commands can be audited
+    let access_frag = if closure.is_some() || no_struct.is_some() {
+        quote!()
+    } else {
+        let body = match &args {
+            SystemArgs::Exclusive { .. } => quote!(
+                let mut access = #ecs_root ::query::Access::default();
+                access.write_all();
+                access
+            ),
+            SystemArgs::System { .. } => quote!(
+                use #ecs_root ::system::System;
+                let mut system = #ecs_root ::system::IntoSystem::into_system(#ident #fn_turbofish);
+                system.initialize(world);
+                system.component_access().clone()
+            ),
+        };
+        quote!(
+            impl #generics #struct_name #generic_names #where_clause {
+                /// The components and resources this command reads and writes, registering any it hasn't seen in `world`
+                ///
+                /// Exclusive commands have access to the whole world
+                #[allow(unused)]
+                #(#lints)*
+                #vis fn access(world: &mut #ecs_root ::world::World) -> #ecs_root ::query::Access<#ecs_root ::component::ComponentId> {
+                    #body
+                }
+            }
+        )
+    };
+
+    // Validates our fields before calling our method, so bad input is rejected before anything is queued
+    let check_method = match (&check, &check_error) {
+        (None, Some(error)) => {
+            return Err(Error::new(error.span(), "`check_error` requires `check`"))
+        }
+        (None, None) => quote!(),
+        (Some(check), _) if skip_world && no_trait => {
+            return Err(Error::new(
+                check.span(),
+                "`check` requires a generated trait",
+            ))
+        }
+        (Some(check), error) => {
+            let try_method = Ident::new(&format!("try_{method}"), method.span());
+            let error = error
+                .clone()
+                .unwrap_or_else(|| parse_quote!(::std::string::String));
+            let ok = if do_return {
+                quote!(&mut Self)
+            } else {
+                quote!(())
+            };
+            let doc = format!(" Calls [`Self::{method}`] if `{}` accepts the arguments, otherwise returns its error", check.to_token_stream().to_string().replace(' ', ""));
+            quote!(
+                #[doc = #doc]
+                fn #try_method #generics (&mut self #(, #fields)*) -> ::core::result::Result<#ok, #error> #where_clause {
+                    #check (#(&#def_field_names),*)?;
+                    ::core::result::Result::Ok(self.#method #fn_turbofish (#(#def_field_names),*))
+                }
+            )
+        }
+    };
+
+    // Calls our method with each preset's fields, keeping tuned values next to the command
+    let variant_methods = match variants.first() {
+        None => Vec::new(),
+        Some(variant) if skip_world && no_trait => {
+            return Err(Error::new(
+                variant.name.span(),
+                "`variants` requires a generated trait",
+            ))
+        }
+        Some(_) => variants
+            .iter()
+            .map(|parse::Variant { name, values }| {
+                if values.len() != def_field_names.len() {
+                    return Err(Error::new(
+                        name.span(),
+                        format!(
+                            "`{name}` has {} values, but the command has {} fields",
+                            values.len(),
+                            def_field_names.len()
+                        ),
+                    ));
+                }
+                let variant_method = Ident::new(&format!("{method}_{name}"), name.span());
+                let doc = format!(
+                    " Calls [`Self::{method}`] with the `{name}` preset: `({})`",
+                    values
+                        .iter()
+                        .map(|value| value.to_token_stream().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                Ok(quote!(
+                    #[doc = #doc]
+                    fn #variant_method #generics (&mut self) #output #where_clause {
+                        self.#method #fn_turbofish (#(#values),*)
+                    }
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    // Applies our entity command to each of the target's children or descendants, found once the command is applied
+    let mut hierarchy_decls = Vec::<TokenStream>::new();
+    let mut hierarchy_commands_impls = Vec::<TokenStream>::new();
+    let mut hierarchy_world_impls = Vec::<TokenStream>::new();
+    let mut hierarchy_forwards = Vec::<TokenStream>::new();
+    match children.or(descendants) {
+        None => (),
+        Some(span) if !entity_command => {
+            return Err(Error::new(
+                span,
+                "`children` and `descendants` only apply to entity commands",
+            ))
+        }
+        Some(span) if closure.is_some() || no_struct.is_some() => {
+            return Err(Error::new(
+                span,
+                "`children` and `descendants` require a generated struct, so cannot be used with `closure` or `no_struct`",
+            ))
+        }
+        Some(span) if skip_world && no_trait => {
+            return Err(Error::new(
+                span,
+                "`children` and `descendants` require a generated trait",
+            ))
+        }
+        Some(_) => {
+            let hierarchy = hierarchy_module(&ecs_root);
+            let command =
+                quote!(#struct_name #struct_turbofish {#(#def_field_names,)* #marker_def});
+            let apply = &method_apply;
+            let (closure_params, bind_params) = entity_command_params(
+                quote!(entity: #ecs_root ::entity::Entity),
+                quote!(world: &mut #ecs_root ::world::World),
+                &ecs_root,
+            );
+            let mut traversals = Vec::new();
+            if children.is_some() {
+                traversals.push((
+                    "children",
+                    quote!(
+                        world
+                            .get::<#hierarchy ::Children>(entity)
+                            .map(|children| children.to_vec())
+                            .unwrap_or_default()
+                    ),
+                ));
+            }
+            if descendants.is_some() {
+                // `iter_descendants` became inherent to `Query` in bevy 0.16
+                let descendants = if bevy_minor() >= 16 {
+                    quote!(query.iter_descendants(entity))
+                } else {
+                    quote!(#hierarchy ::HierarchyQueryExt::iter_descendants(&query, entity))
+                };
+                traversals.push((
+                    "descendants",
+                    quote!({
+                        let mut state = #ecs_root ::system::SystemState::<#ecs_root ::system::Query<&#hierarchy ::Children>>::new(world);
+                        let query = state.get(world);
+                        #descendants.collect::<::std::vec::Vec<_>>()
+                    }),
+                ));
+            }
+            for (suffix, targets) in traversals {
+                let hierarchy_method = Ident::new(&format!("{method}_{suffix}"), method.span());
+                let params = quote!(&mut self #(, #fields)*);
+                let apply = apply_entity_command(apply, &command, quote!(entity), quote!(world));
+                let body = quote!(
+                    let targets = #targets;
+                    for entity in targets {
+                        #(let #def_field_names = ::core::clone::Clone::clone(&#def_field_names);)*
+                        #apply;
+                    }
+                );
+                let doc = format!(" Applies [`Self::{method}`] to each of the entity's {suffix}, as found when the command is applied");
+                hierarchy_decls.push(quote!(
+                    #[doc = #doc]
+                    fn #hierarchy_method #generics (#params) #output #where_clause;
+                ));
+                hierarchy_commands_impls.push(quote!(
+                    fn #hierarchy_method #generics (#params) #output #where_clause {
+                        self.#queue(move |#closure_params| {
+                            #bind_params
+                            #body
+                        });
+                        #return_frag
+                    }
+                ));
+                hierarchy_world_impls.push(quote!(
+                    fn #hierarchy_method #generics (#params) #output #where_clause {
+                        let entity = self.id();
+                        self.world_scope(|world| {
+                            #body
+                        });
+                        #return_frag
+                    }
+                ));
+                hierarchy_forwards.push(quote!(
+                    fn #hierarchy_method #generics (#params) #output #where_clause {
+                        #trait_name :: #hierarchy_method #fn_turbofish (::core::ops::DerefMut::deref_mut(self) #(, #def_field_names)*);
+                        #return_frag
+                    }
+                ));
+            }
+        }
+    }
+
+    // Generates a new trait + method for issuing our command
+    // Implements this new trait for `Commands` or `EntityCommands`
+    let commands_trait_frag = match &args {
         SystemArgs::Exclusive { .. } => {
-            if no_trait || no_world {
+            if no_trait {
                 quote!()
-            } else if entity_command {
+            } else {
+                let commands_struct = if entity_command {
+                    quote!(EntityCommands<'_>)
+                } else {
+                    quote!(Commands<'_, '_>)
+                };
                 quote!(
-                    impl #trait_name for #ecs_root ::world::EntityWorldMut<'_> {
-                        fn #name #generics (&mut self, #(#fields,)*) #output {
-                            let id = self.id();
-                            self.world_scope(|world| {
-                                <#struct_name #generic_names as #ecs_root ::system:: #command_trait>::apply (#struct_name {#(#def_field_names,)*}, id, world);
-                            });
+                    #hidden_trait
+                    #trait_vis trait #trait_name #sealed_bound {
+                        #docs
+                        fn #method #generics (&mut self, #(#fields,)*) #output #where_clause;
+                        #check_method
+                        #(#variant_methods)*
+                        #(#hierarchy_decls)*
+                    }
+
+                    impl #trait_name for #ecs_root ::system:: #commands_struct {
+                        fn #method #generics (&mut self, #(#fields,)*) #output #where_clause {
+                            self.#queue(#queued_frag);
                             #return_frag
                         }
+                        #(#hierarchy_commands_impls)*
                     }
                 )
+            }
+        }
+        SystemArgs::System { .. } => {
+            if no_trait {
+                quote!()
             } else {
+                let commands_struct = if entity_command {
+                    quote!(EntityCommands<'_>)
+                } else {
+                    quote!(Commands<'_, '_>)
+                };
+
                 quote!(
-                    impl #trait_name for #ecs_root ::world::World {
-                        fn #name #generics (&mut self, #(#fields,)*) #output {
-                            <#struct_name #generic_names as #ecs_root ::system:: #command_trait>::apply (#struct_name {#(#def_field_names,)*}, self);
+                    #hidden_trait
+                    #trait_vis trait #trait_name #sealed_bound {
+                        #docs
+                        fn #method #generics (&mut self #(, #fields)*) #output #where_clause;
+                        #check_method
+                        #(#variant_methods)*
+                        #(#hierarchy_decls)*
+                    }
+
+                    impl #trait_name for #ecs_root ::system:: #commands_struct {
+                        fn #method #generics (&mut self #(, #fields)*) #output #where_clause {
+                            self.#queue(#queued_frag);
                             #return_frag
                         }
+                        #(#hierarchy_commands_impls)*
                     }
                 )
             }
         }
-        SystemArgs::System { entity_name, .. } => {
+    };
+
+    // Declares the world trait when it's separate from the commands trait
+    let world_trait_frag = match &world_trait_name {
+        Some(world_trait_name) if !skip_world => quote!(
+            #hidden_trait
+            #world_trait_vis trait #world_trait_name #sealed_bound {
+                #docs
+                fn #method #generics (&mut self #(, #fields)*) #output #where_clause;
+                #check_method
+                #(#variant_methods)*
+                #(#hierarchy_decls)*
+            }
+        ),
+        _ => quote!(),
+    };
+
+    // Forwards the same trait to any extra types which deref to one of the types above
+    let also_impl_frag = if no_trait {
+        quote!()
+    } else {
+        let also_impls = also_impl.iter().map(|ty| {
+            quote!(
+                impl #trait_name for #ty {
+                    fn #method #generics (&mut self #(, #fields)*) #output #where_clause {
+                        #trait_name :: #method #fn_turbofish (::core::ops::DerefMut::deref_mut(self) #(, #def_field_names)*);
+                        #return_frag
+                    }
+                    #(#hierarchy_forwards)*
+                }
+            )
+        });
+        quote!(#(#also_impls)*)
+    };
+
+    // Forwards the world trait to types wrapping a world, eg. the render app during plugin build
+    let world_target_frag = match world_target_types.first() {
+        None => quote!(),
+        Some(ty) if entity_command => {
+            return Err(Error::new(
+                ty.span(),
+                "`sub_app` and `world_impl` only apply to commands",
+            ))
+        }
+        Some(ty) if skip_world => {
+            return Err(Error::new(
+                ty.span(),
+                "`sub_app` and `world_impl` require the `World` method",
+            ))
+        }
+        Some(_) => {
+            let impls = world_targets.iter().map(|(ty, world, _)| {
+                quote!(
+                    impl #world_trait for #ty {
+                        fn #method #generics (&mut self #(, #fields)*) #output #where_clause {
+                            #world_trait :: #method #fn_turbofish (#world #(, #def_field_names)*);
+                            #return_frag
+                        }
+                    }
+                )
+            });
+            quote!(#(#impls)*)
+        }
+    };
+
+    // Implements the same trait as above, but for `World` or `EntityWorldMut`
+    // limited, budgeted and cached commands always go through `apply`, which counts, buffers or reuses their system
+    let impl_world_frag = match &args {
+        SystemArgs::System {
+            entity_name,
+            systems_in,
+        } if max_per_frame.is_none() && buffered.is_none() && cached.is_none() => {
             let root = if entity_command {
                 quote!(#ecs_root ::world::EntityWorldMut<'_>)
             } else {
@@ -309,54 +1688,1191 @@ pub fn commandify(
             let run_frag = if entity_command {
                 quote!(
                     self.world_scope(|world| {
-                        world.run_system_once_with(#system_in_frag, #ident);
+                        world.#run_with #ran;
                     });
                 )
             } else {
-                quote!(self.run_system_once_with(#system_in_frag, #ident);)
+                quote!(self.#run_with #ran;)
             };
 
-            if no_trait || no_world {
+            if skip_world {
                 quote!()
-            } else if fields.is_empty() {
+            } else if systems_in.is_empty() {
                 quote!(
-                    impl #trait_name for #root {
-                        fn #name #generics (&mut self)  #output {
-                            use ::bevy::ecs::system::RunSystemOnce;
-                            self.run_system_once(#ident);
+                    impl #world_trait for #root {
+                        fn #method #generics (&mut self) #output #where_clause {
+                            use #ecs_root ::system::RunSystemOnce;
+                            self.run_system_once(#ident #fn_turbofish)#ran;
                             #return_frag
                         }
+                        #(#hierarchy_world_impls)*
                     }
                 )
             } else {
                 quote!(
-                    impl #trait_name for #root {
-                        fn #name #generics (&mut self #(,#fields)*)  #output {
-                            use ::bevy::ecs::system::RunSystemOnce;
+                    impl #world_trait for #root {
+                        fn #method #generics (&mut self #(,#fields)*) #output #where_clause {
+                            use #ecs_root ::system::RunSystemOnce;
                             #entity_frag
                             #run_frag
                             #return_frag
                         }
+                        #(#hierarchy_world_impls)*
+                    }
+                )
+            }
+        }
+        _ => {
+            if skip_world {
+                quote!()
+            } else if entity_command {
+                let apply =
+                    apply_entity_command(&apply_frag, &queued_frag, quote!(id), quote!(world));
+                quote!(
+                    impl #world_trait for #ecs_root ::world::EntityWorldMut<'_> {
+                        fn #method #generics (&mut self, #(#fields,)*) #output #where_clause {
+                            let id = self.id();
+                            self.world_scope(|world| {
+                                #apply;
+                            });
+                            #return_frag
+                        }
+                        #(#hierarchy_world_impls)*
+                    }
+                )
+            } else {
+                quote!(
+                    impl #world_trait for #ecs_root ::world::World {
+                        fn #method #generics (&mut self, #(#fields,)*) #output #where_clause {
+                            #apply_frag (#queued_frag, self);
+                            #return_frag
+                        }
+                    }
+                )
+            }
+        }
+    };
+
+    // Generates `Commands` and `World` methods which take their target entities explicitly
+    let commands_method_frag = match commands.or(all).or(both).or(spawns) {
+        None => quote!(),
+        Some(span) if !entity_command => {
+            return Err(Error::new(
+                span,
+                "`commands`, `all`, `both` and `spawns` only apply to entity commands",
+            ))
+        }
+        Some(span) if closure.is_some() => {
+            return Err(Error::new(
+                span,
+                "`commands`, `all`, `both` and `spawns` cannot be used with `closure`",
+            ))
+        }
+        Some(span) if spawns.is_some() && commands.or(both).is_some() => {
+            return Err(Error::new(
+                span,
+                format!("`spawns` cannot be used with `commands` or `both`, as each names its `Commands` method `{method}`"),
+            ))
+        }
+        Some(span) => {
+            let Some(target) = target else {
+                return Err(Error::new(
+                    entity.as_ref().map_or(span, |entity| entity.span()),
+                    "`commands`, `all`, `both` and `spawns` require the target entity to be a plain name",
+                ));
+            };
+            let command =
+                quote!(#struct_name #struct_turbofish {#(#def_field_names,)* #marker_def});
+            let apply = &method_apply;
+
+            let mut decls = Vec::new();
+            let mut commands_impls = Vec::new();
+            let mut world_impls = Vec::new();
+
+            // `both` also generates a plain command, so implies the `Commands` method
+            if commands.or(both).is_some() {
+                let params = quote!(&mut self, #target: #ecs_root ::entity::Entity #(, #fields)*);
+                decls.push(quote!(
+                    #docs
+                    fn #method #generics (#params) #output #where_clause;
+                ));
+                commands_impls.push(quote!(
+                    fn #method #generics (#params) #output #where_clause {
+                        self.entity(#target).#queue(#queued_frag);
+                        #return_frag
+                    }
+                ));
+                let apply = apply_entity_command(apply, &command, &target, quote!(self));
+                world_impls.push(quote!(
+                    fn #method #generics (#params) #output #where_clause {
+                        #apply;
+                        #return_frag
+                    }
+                ));
+            }
+
+            // queues a single command, which applies ours to each entity in turn
+            if all.is_some() {
+                let all_name = Ident::new(&format!("{method}_all"), method.span());
+                let params = quote!(
+                    &mut self,
+                    entities: impl ::core::iter::IntoIterator<Item = #ecs_root ::entity::Entity>
+                    #(, #fields)*
+                );
+                let clone_fields = quote!(#(let #def_field_names = ::core::clone::Clone::clone(&#def_field_names);)*);
+                let apply_world = apply_entity_command(apply, &command, quote!(entity), quote!(world));
+                let apply_self = apply_entity_command(apply, &command, quote!(entity), quote!(self));
+                decls.push(quote!(
+                    #docs
+                    fn #all_name #generics (#params) #output #where_clause;
+                ));
+                commands_impls.push(quote!(
+                    fn #all_name #generics (#params) #output #where_clause {
+                        let entities = entities.into_iter().collect::<::std::vec::Vec<_>>();
+                        self.#queue(move |world: &mut #ecs_root ::world::World| {
+                            for entity in entities {
+                                #clone_fields
+                                #apply_world;
+                            }
+                        });
+                        #return_frag
+                    }
+                ));
+                world_impls.push(quote!(
+                    fn #all_name #generics (#params) #output #where_clause {
+                        for entity in entities {
+                            #clone_fields
+                            #apply_self;
+                        }
+                        #return_frag
+                    }
+                ));
+            }
+
+            // spawns our target, handing it back so that more may be added to it
+            if spawns.is_some() {
+                let params = quote!(&mut self #(, #fields)*);
+                decls.push(quote!(
+                    /// The entity spawned by our method, either `EntityCommands` or `EntityWorldMut`
+                    type Spawned<'a> where Self: 'a;
+                    #docs
+                    fn #method #generics (#params) -> Self::Spawned<'_> #where_clause;
+                ));
+                commands_impls.push(quote!(
+                    type Spawned<'a> = #ecs_root ::system::EntityCommands<'a> where Self: 'a;
+                    fn #method #generics (#params) -> Self::Spawned<'_> #where_clause {
+                        let mut entity = self.spawn_empty();
+                        entity.#queue(#queued_frag);
+                        entity
+                    }
+                ));
+                let apply = apply_entity_command(apply, &command, quote!(entity), quote!(self));
+                world_impls.push(quote!(
+                    type Spawned<'a> = #ecs_root ::world::EntityWorldMut<'a> where Self: 'a;
+                    fn #method #generics (#params) -> Self::Spawned<'_> #where_clause {
+                        let entity = self.spawn_empty().id();
+                        #apply;
+                        self.entity_mut(entity)
+                    }
+                ));
+            }
+
+            let impl_world_frag = if no_world {
+                quote!()
+            } else {
+                quote!(
+                    impl #commands_trait_name for #ecs_root ::world::World {
+                        #(#world_impls)*
+                    }
+                )
+            };
+
+            // a regular command where the target entity is just another field
+            let plain_command_frag = if both.is_some() {
+                let plain_apply = apply_entity_command(
+                    quote!(<#struct_name #generic_names as #ecs_root :: #command_trait>::apply),
+                    &command,
+                    &target,
+                    quote!(world),
+                );
+                quote!(
+                    #hidden_struct
+                    #(#item_attrs)*
+                    #vis struct #plain_name #generics #where_clause {
+                        pub #target: #ecs_root ::entity::Entity,
+                        #(#field_doc_attrs pub #fields,)*
+                        #marker_field
+                    }
+
+                    impl #generics #ecs_root :: #command_module ::Command for #plain_name #generic_names #where_clause {
+                        fn apply(self, world: &mut #ecs_root ::world::World) {
+                            let #plain_name {#target, #(#def_field_names,)* #marker_rest} = self;
+                            #plain_apply;
+                        }
+                    }
+                )
+            } else {
+                quote!()
+            };
+
+            quote!(
+                #plain_command_frag
+
+                #hidden_trait
+
+                #trait_vis trait #commands_trait_name #sealed_bound {
+                    #(#decls)*
+                }
+
+                impl #commands_trait_name for #ecs_root ::system::Commands<'_, '_> {
+                    #(#commands_impls)*
+                }
+
+                #impl_world_frag
+            )
+        }
+    };
+
+    // Generates an `EntityCommands` and `EntityWorldMut` method for commands which optionally take an entity
+    let entity_method_frag = match optional_target {
+        Some(target) if !no_trait && closure.is_none() => {
+            let (other_fields, other_names): (Vec<_>, Vec<_>) = fields
+                .iter()
+                .zip(&def_field_names)
+                .filter(|(_, name)| name.to_string() != target.to_string())
+                .unzip();
+            let params = quote!(&mut self #(, #other_fields)*);
+            let command = quote!(
+                #struct_name #struct_turbofish {
+                    #target: ::core::option::Option::Some(id),
+                    #(#other_names,)*
+                    #marker_def
+                }
+            );
+            let (closure_params, bind_params) = entity_command_params(
+                quote!(id: #ecs_root ::entity::Entity),
+                quote!(world: &mut #ecs_root ::world::World),
+                &ecs_root,
+            );
+            let impl_world_frag = if no_world {
+                quote!()
+            } else {
+                quote!(
+                    impl #entity_trait_name for #ecs_root ::world::EntityWorldMut<'_> {
+                        fn #method #generics (#params) #output #where_clause {
+                            let id = self.id();
+                            self.world_scope(|world| {
+                                #method_apply (#command, world);
+                            });
+                            #return_frag
+                        }
+                    }
+                )
+            };
+
+            quote!(
+                #hidden_trait
+                #trait_vis trait #entity_trait_name #sealed_bound {
+                    #docs
+                    fn #method #generics (#params) #output #where_clause;
+                }
+
+                impl #entity_trait_name for #ecs_root ::system::EntityCommands<'_> {
+                    fn #method #generics (#params) #output #where_clause {
+                        self.#queue(move |#closure_params| {
+                            #bind_params
+                            #method_apply (#command, world);
+                        });
+                        #return_frag
+                    }
+                }
+
+                #impl_world_frag
+            )
+        }
+        _ => quote!(),
+    };
+
+    // Generates an `App` method which queues our command once during `Startup`
+    let app_frag = match &app {
+        None => quote!(),
+        Some(app) if entity_command => {
+            return Err(Error::new(app.span(), "`app` only applies to commands"))
+        }
+        Some(app) => {
+            let app_method = Ident::new(&format!("queue_{method}"), method.span());
+            let params = quote!(&mut self #(, #fields)*);
+
+            // queues a copy of our command whenever the given state is entered or exited
+            let state_module = state_module(&ecs_root);
+            let mut state_generics = generics.clone();
+            state_generics
+                .params
+                .push(parse_quote!(__S: #state_module ::States));
+            let mut state_decls = Vec::new();
+            let mut state_impls = Vec::new();
+            for (span, schedule, suffix) in [
+                (run_on_enter, quote!(OnEnter), "on_enter"),
+                (run_on_exit, quote!(OnExit), "on_exit"),
+            ] {
+                if span.is_none() {
+                    continue;
+                }
+                let state_method = Ident::new(&format!("queue_{method}_{suffix}"), method.span());
+                let params = quote!(&mut self, state: __S #(, #fields)*);
+                state_decls.push(quote!(
+                    #docs
+                    fn #state_method #state_generics (#params) -> &mut Self #where_clause;
+                ));
+                state_impls.push(quote!(
+                    fn #state_method #state_generics (#params) -> &mut Self #where_clause {
+                        self.add_systems(#state_module :: #schedule (state), move |mut commands: #ecs_root ::system::Commands| {
+                            #(let #def_field_names = ::core::clone::Clone::clone(&#def_field_names);)*
+                            commands.#queue(#queued_frag);
+                        })
+                    }
+                ));
+            }
+
+            quote!(
+                #hidden_trait
+                #trait_vis trait #app_trait_name #sealed_bound {
+                    #docs
+                    fn #app_method #generics (#params) -> &mut Self #where_clause;
+                    #(#state_decls)*
+                }
+
+                impl #app_trait_name for #app ::App {
+                    fn #app_method #generics (#params) -> &mut Self #where_clause {
+                        // systems may run more than once, so hand the command over only the first time
+                        let mut command = ::core::option::Option::Some(#queued_frag);
+                        self.add_systems(#app ::Startup, move |mut commands: #ecs_root ::system::Commands| {
+                            if let ::core::option::Option::Some(command) = command.take() {
+                                commands.#queue(command);
+                            }
+                        })
+                    }
+                    #(#state_impls)*
+                }
+            )
+        }
+    };
+
+    // a plugin queueing our command with default fields whenever its key, or chord of keys, is pressed
+    let hotkey_frag = match &hotkey {
+        None => quote!(),
+        Some(hotkey) if entity_command => {
+            return Err(Error::new(
+                hotkey.span(),
+                "`hotkey` only applies to commands",
+            ))
+        }
+        Some(hotkey) if !generics.params.is_empty() => {
+            return Err(Error::new(
+                hotkey.span(),
+                "`hotkey` cannot be used with generic commands",
+            ))
+        }
+        Some(hotkey) => {
+            let keys = match hotkey {
+                syn::Expr::Array(array) => array.elems.iter().collect::<Vec<_>>(),
+                key => vec![key],
+            };
+            let plugin_name = Ident::new(
+                &format!("{}HotkeyPlugin", name.to_string().to_pascal_case()),
+                name.span(),
+            );
+            let app = app
+                .clone()
+                .unwrap_or_else(|| sibling_crate(&ecs_root, "app"));
+            let input = sibling_crate(&ecs_root, "input");
+            let doc = format!(
+                " Queues `{method}` with default arguments whenever `{}` is pressed",
+                hotkey.to_token_stream().to_string().replace(' ', "")
+            );
+            quote!(
+                #[doc = #doc]
+                #vis struct #plugin_name;
+
+                impl #app ::Plugin for #plugin_name {
+                    fn build(&self, app: &mut #app ::App) {
+                        app.add_systems(
+                            #app ::Update,
+                            |keys: #ecs_root ::system::Res<#input ::ButtonInput<#input ::keyboard::KeyCode>>, mut commands: #ecs_root ::system::Commands| {
+                                let chord = [#(#keys),*];
+                                if keys.all_pressed(chord) && keys.any_just_pressed(chord) {
+                                    #(let #def_field_names = ::core::default::Default::default();)*
+                                    commands.#queue(#queued_frag);
+                                }
+                            },
+                        );
+                    }
+                }
+            )
+        }
+    };
+
+    // lets fuzzers and property tests generate instances of our struct, optionally behind a feature of the caller's
+    let arbitrary_frag = match &arbitrary {
+        None => quote!(),
+        Some(Arbitrary::Always(_)) => quote!(#[derive(::arbitrary::Arbitrary)]),
+        Some(Arbitrary::Feature(feature)) => {
+            quote!(#[cfg_attr(feature = #feature, derive(::arbitrary::Arbitrary))])
+        }
+    };
+
+    // an existing struct may stand in for ours, so long as its fields are named after our params
+    let struct_frag = if closure.is_some() || no_struct.is_some() {
+        quote!()
+    } else {
+        quote!(
+            #hidden_struct
+            #arbitrary_frag
+            #(#item_attrs)*
+            #vis
+            #constness
+            #asyncness
+            #unsafety
+            #abi
+            struct
+            #struct_name
+            #struct_generics
+            #struct_fields_frag
+        )
+    };
+
+    // Generates a free function returning our command, in the style of bevy's own command functions
+    let free_constructor_frag = match constructor {
+        None => quote!(),
+        Some(constructor) => {
+            let constructor = match constructor {
+                Constructor::Default(_) => {
+                    Ident::new(&struct_name.to_string().to_snake_case(), struct_name.span())
+                }
+                Constructor::Named(ident) => respan(ident),
+            };
+            let returned = if entity_command {
+                quote!(impl #ecs_root ::system::EntityCommand)
+            } else {
+                quote!(impl #ecs_root :: #command_module ::Command)
+            };
+            quote!(
+                #docs
+                #vis fn #constructor #generics (#(#fields),*) -> #returned #where_clause {
+                    #queued_frag
+                }
+            )
+        }
+    };
+
+    // Generates a smoke test applying our command with default fields to an empty world
+    let test_frag = match test {
+        None => quote!(),
+        Some(span) if !generics.params.is_empty() => {
+            return Err(Error::new(
+                span,
+                "`test` cannot be used with generic commands",
+            ))
+        }
+        Some(_) => {
+            let test_name = Ident::new(
+                &format!("{}_smoke_test", struct_name.to_string().to_snake_case()),
+                ident.span(),
+            );
+            let apply = if entity_command {
+                let apply =
+                    apply_entity_command(&apply_frag, &queued_frag, quote!(entity), quote!(world));
+                quote!(
+                    let entity = world.spawn_empty().id();
+                    {
+                        let world = &mut world;
+                        #apply;
                     }
                 )
+            } else {
+                quote!(#apply_frag (#queued_frag, &mut world);)
+            };
+            // buffered commands only run once drained, so drain them and check nothing was left behind
+            let drain = if buffered.is_some() {
+                quote!(
+                    #drain_name(&mut world);
+                    assert!(
+                        world
+                            .get_resource::<#budget_name>()
+                            .map_or(true, |buffer| buffer.pending.is_empty()),
+                        "the buffered command was not applied when drained",
+                    );
+                )
+            } else {
+                quote!()
+            };
+            quote!(
+                #[cfg(test)]
+                #[test]
+                fn #test_name() {
+                    let mut world = #ecs_root ::world::World::new();
+                    #(let #fields = ::core::default::Default::default();)*
+                    #apply
+                    #drain
+                }
+            )
+        }
+    };
+
+    // a stable name for registries and consoles to refer to our command by, with metadata for tooling
+    let name_frag = if closure.is_some() || no_struct.is_some() {
+        quote!()
+    } else {
+        let name = name.to_string();
+        let doc = parse::doc_summary(&attrs);
+        let field_meta = fields.iter().map(|field| {
+            let field = field.to_string();
+            let (field_name, ty) = field.split_once(':').unwrap_or((&field, ""));
+            let (field_name, ty) = (field_name.trim(), type_name(ty));
+            quote!((#field_name, #ty))
+        });
+        let file = quote::quote_spanned!(ident.span()=> ::core::file!());
+        let line = quote::quote_spanned!(ident.span()=> ::core::line!());
+        quote!(
+            impl #generics #struct_name #generic_names #where_clause {
+                /// The name of this command, as given to the macro
+                #vis const NAME: &'static str = #name;
+                /// The first paragraph of this command's docs
+                #vis const DOC: &'static str = #doc;
+                /// The name and type of each field, in declaration order
+                #vis const FIELDS: &'static [(&'static str, &'static str)] = &[#(#field_meta),*];
+                /// The source file this command was declared in
+                #vis const FILE: &'static str = #file;
+                /// The line this command was declared on
+                #vis const LINE: u32 = #line;
+            }
+        )
+    };
+
+    // field-less commands have a single value, ready made for `const` items and data tables
+    let instance_frag = if closure.is_none() && no_struct.is_none() && fields.is_empty() {
+        quote!(
+            impl #generics #struct_name #generic_names #where_clause {
+                /// This command, which has no fields to set
+                #vis const INSTANCE: Self = Self {#marker_def};
+            }
+        )
+    } else {
+        quote!()
+    };
+
+    // Lets our struct double as a system, eg. to add to a schedule or register as a one-shot system
+    let into_system_frag = match into_system {
+        None => quote!(),
+        Some(span) if closure.is_some() || no_struct.is_some() => {
+            return Err(Error::new(
+                span,
+                "`into_system` requires a generated struct, so cannot be used with `closure` or `no_struct`",
+            ))
+        }
+        Some(span) if !generics.params.is_empty() => {
+            return Err(Error::new(
+                span,
+                "`into_system` cannot be used with generic commands",
+            ))
+        }
+        Some(_) => {
+            let apply = quote!(<#struct_name as #ecs_root :: #command_trait>::apply);
+            let command = quote!(Self {#(#def_field_names,)*});
+            let (input, params, apply) = if entity_command {
+                (
+                    system_input(quote!(#ecs_root ::entity::Entity), &ecs_root),
+                    quote!(#ecs_root ::system::In(entity): #ecs_root ::system::In<#ecs_root ::entity::Entity>, world: &mut #ecs_root ::world::World),
+                    apply_entity_command(apply, command, quote!(entity), quote!(world)),
+                )
+            } else {
+                (
+                    quote!(()),
+                    quote!(world: &mut #ecs_root ::world::World),
+                    quote!(#apply (#command, world)),
+                )
+            };
+            let doc = if entity_command {
+                " Converts this command into a system which applies a copy of it to the input entity on each run"
+            } else {
+                " Converts this command into a system which applies a copy of it on each run"
+            };
+            quote!(
+                impl #struct_name {
+                    #[doc = #doc]
+                    #vis fn into_system(self) -> impl #ecs_root ::system::System<In = #input, Out = ()> {
+                        let Self { #(#def_field_names,)* .. } = self;
+                        #ecs_root ::system::IntoSystem::into_system(move |#params| {
+                            #(let #def_field_names = ::core::clone::Clone::clone(&#def_field_names);)*
+                            #apply;
+                        })
+                    }
+                }
+            )
+        }
+    };
+
+    // Lets entity commands be built up front and bound to an entity, without an `EntityCommands`
+    let constructor_frag = if entity_command && closure.is_none() && no_struct.is_none() {
+        let bound_apply = apply_entity_command(
+            quote!(<Self as #ecs_root :: #command_trait>::apply),
+            quote!(self),
+            quote!(entity),
+            quote!(world),
+        );
+        quote!(
+            #[allow(clippy::new_without_default)]
+            impl #generics #struct_name #generic_names #where_clause {
+                /// Creates this command from its fields, usable in `const` items
+                #vis const fn new(#(#fields),*) -> Self {
+                    Self {#(#def_field_names,)* #marker_def}
+                }
+
+                /// Binds this command to `entity`, yielding a plain `Command`
+                #vis fn with_entity(self, entity: #ecs_root ::entity::Entity) -> impl #ecs_root :: #command_module ::Command {
+                    move |world: &mut #ecs_root ::world::World| {
+                        #bound_apply;
+                    }
+                }
             }
+        )
+    } else {
+        quote!()
+    };
+
+    // only our impls name the fn, so they join it in a block which the module cannot see into
+    let (fn_frag, hidden_frag) = match no_original {
+        Some(span) if closure.is_some() => {
+            return Err(Error::new(
+                span,
+                "`no_original` cannot be used with `closure`, as its methods call the fn",
+            ))
         }
+        Some(_) => (
+            quote!(),
+            quote!(
+                const _: () = {
+                    #fn_frag
+                    #impl_command_frag
+                    #batch_frag
+                    #access_frag
+                    #impl_world_frag
+                };
+            ),
+        ),
+        None => (
+            quote!(
+                #fn_frag
+                #impl_command_frag
+                #batch_frag
+                #access_frag
+                #impl_world_frag
+            ),
+            quote!(),
+        ),
     };
 
     Ok(quote!(
         #fn_frag
-        #(#attrs)*
-        #vis
-        #constness
-        #asyncness
-        #unsafety
-        #abi
-        struct
-        #struct_name
-        #generics
-        #struct_fields_frag
-        #impl_command_frag
+        #hidden_frag
+        #struct_frag
+        #name_frag
+        #instance_frag
+        #constructor_frag
+        #into_system_frag
+        #free_constructor_frag
+        #test_frag
+        #cache_frag
+        #limit_frag
+        #budget_frag
+        #scope_frag
         #commands_trait_frag
-        #impl_world_frag
+        #world_trait_frag
+        #also_impl_frag
+        #world_target_frag
+        #commands_method_frag
+        #entity_method_frag
+        #app_frag
+        #hotkey_frag
+        #sealed_frag
+        #(#warnings)*
     ))
 }
+
+pub fn commandify_trait(
+    args: Punctuated<Meta, syn::Token![,]>,
+    mut item: ItemTrait,
+) -> Result<TokenStream, Error> {
+    // `delegate` is ours, any other args apply to every command
+    let mut delegate = None;
+    let mut shared_args = Vec::new();
+    for meta in args {
+        match meta {
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("delegate") => {
+                delegate = Some(value.try_to_type()?);
+            }
+            meta => shared_args.push(meta),
+        }
+    }
+    let Some(delegate) = delegate else {
+        return Err(Error::new(
+            item.ident.span(),
+            "`commandify_trait` requires `delegate = T`, naming the type whose impl the commands call",
+        ));
+    };
+    if !item.generics.params.is_empty() {
+        return Err(Error::new(
+            item.generics.span(),
+            "`commandify_trait` does not support generic traits",
+        ));
+    }
+
+    let trait_name = &item.ident;
+    let mut commands = Vec::new();
+    for trait_item in &mut item.items {
+        let TraitItem::Fn(TraitItemFn { attrs, sig, .. }) = trait_item else {
+            continue;
+        };
+        if let Some(receiver) = sig.receiver() {
+            return Err(Error::new_spanned(
+                receiver,
+                "`commandify_trait` methods cannot take `self`, as commands call them on `delegate` as associated fns",
+            ));
+        }
+
+        // methods may be marked as entity commands, or given args of their own
+        let mut entity_command = false;
+        let mut method_args = shared_args.clone();
+        let mut fn_attrs = Vec::new();
+        for attr in attrs.drain(..) {
+            let is_entity = attr.path().is_ident("entity_command");
+            if !is_entity && !attr.path().is_ident("command") {
+                fn_attrs.push(attr);
+                continue;
+            }
+            entity_command = is_entity;
+            if let Meta::List(_) = attr.meta {
+                method_args.extend(
+                    attr.parse_args_with(Punctuated::<Meta, syn::Token![,]>::parse_terminated)?,
+                );
+            }
+        }
+        // everything but lints and docs belongs to the command, not the trait
+        attrs.extend(
+            fn_attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc") || parse::is_param_attr(attr))
+                .cloned(),
+        );
+
+        let mut forward_args = Vec::new();
+        for input in &sig.inputs {
+            if let FnArg::Typed(pt) = input {
+                let Pat::Ident(pat) = &*pt.pat else {
+                    return Err(Error::new(
+                        pt.pat.span(),
+                        "`commandify_trait` methods must name their params",
+                    ));
+                };
+                let ident = &pat.ident;
+                forward_args.push(quote!(#ident));
+            }
+        }
+        let generic_args = sig
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(inner) => Some(&inner.ident),
+                GenericParam::Const(inner) => Some(&inner.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let turbofish = if generic_args.is_empty() {
+            quote!()
+        } else {
+            quote!(::< #(#generic_args),* >)
+        };
+
+        // each method becomes a fn calling the delegate's impl, which is then commandified as usual
+        let method = &sig.ident;
+        let command_fn = ItemFn {
+            attrs: fn_attrs,
+            vis: item.vis.clone(),
+            sig: sig.clone(),
+            block: parse_quote!({
+                <#delegate as #trait_name>::#method #turbofish (#(#forward_args),*)
+            }),
+        };
+        commands.push(commandify(
+            method_args.into_iter().collect(),
+            command_fn,
+            entity_command,
+        )?);
+
+        // helper attributes and param docs aren't allowed on the trait itself
+        sig.inputs = parse::strip_helper_attrs(&sig.inputs);
+    }
+
+    Ok(quote!(
+        #item
+        #(#commands)*
+    ))
+}
+
+pub fn command_sequence(
+    args: Punctuated<Meta, syn::Token![,]>,
+    mut item: ItemFn,
+) -> Result<TokenStream, Error> {
+    for input in &item.sig.inputs {
+        if let FnArg::Typed(pt) = input {
+            if matches!(&*pt.pat, Pat::Ident(pat) if pat.ident == "world") {
+                return Err(Error::new(
+                    pt.pat.span(),
+                    "`command_sequence` applies its steps to `world`, so params cannot take its name",
+                ));
+            }
+        }
+    }
+
+    // each step calls the `World` method of another command, so that they all apply in order
+    let mut steps = Vec::new();
+    for stmt in &item.block.stmts {
+        let expr = match stmt {
+            Stmt::Expr(expr, _) => expr,
+            stmt => {
+                return Err(Error::new(
+                    stmt.span(),
+                    "`command_sequence` steps are calls to other commands, eg. `spawn_enemy(pos, 3);` or `entity.heal(10);`",
+                ))
+            }
+        };
+        let step = match expr {
+            Expr::Call(ExprCall { func, args, .. }) => {
+                let method = match &**func {
+                    Expr::Path(ExprPath { path, qself: None, .. }) => path.get_ident(),
+                    _ => None,
+                };
+                let Some(method) = method else {
+                    return Err(Error::new(
+                        func.span(),
+                        "`command_sequence` steps name the method of a command in scope, rather than a path",
+                    ));
+                };
+                quote::quote_spanned!(expr.span()=> world.#method(#args);)
+            }
+            // entity commands are applied to the entity they're called on
+            Expr::MethodCall(ExprMethodCall {
+                receiver,
+                method,
+                turbofish,
+                args,
+                ..
+            }) => {
+                quote::quote_spanned!(expr.span()=> world.entity_mut(#receiver).#method #turbofish (#args);)
+            }
+            expr => {
+                return Err(Error::new(
+                    expr.span(),
+                    "`command_sequence` steps are calls to other commands, eg. `spawn_enemy(pos, 3);` or `entity.heal(10);`",
+                ))
+            }
+        };
+        steps.push(step);
+    }
+
+    // the sequence becomes an exclusive command, which is then commandified as usual
+    let ecs_root = parse::macro_args(&args, item.sig.ident.clone())?
+        .ecs_root
+        .unwrap_or_else(|| parse_quote!(::bevy::ecs));
+    item.sig
+        .inputs
+        .insert(0, parse_quote!(world: &mut #ecs_root ::world::World));
+    item.block = parse_quote!({
+        #(#steps)*
+    });
+
+    commandify(args, item, false)
+}
+
+pub fn command_group(group: CommandGroup) -> TokenStream {
+    let CommandGroup {
+        attrs,
+        vis,
+        name,
+        traits,
+    } = group;
+
+    // paths are written relative to the invocation, which is now our parent module
+    let traits = traits.into_iter().map(|item| {
+        let GroupItem {
+            leading_colon,
+            tree,
+        } = item;
+        let tree = match tree {
+            _ if leading_colon.is_some() => tree,
+            UseTree::Path(path) if path.ident == "crate" => UseTree::Path(path),
+            UseTree::Path(mut path) if path.ident == "self" => {
+                path.ident = Ident::new("super", path.ident.span());
+                UseTree::Path(path)
+            }
+            tree => parse_quote!(super::#tree),
+        };
+        quote!(#leading_colon #tree)
+    });
+
+    quote!(
+        #(#attrs)*
+        #vis mod #name {
+            #(pub use #traits;)*
+        }
+    )
+}
+
+/// Warns at `span` during expansion, as stable proc macros have no diagnostics API
+/// The warning comes from using a deprecated constant, whose note carries our message
+fn warning(span: Span, message: &str) -> TokenStream {
+    quote::quote_spanned!(span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const commandify_warning: () = ();
+            commandify_warning
+        };
+    )
+}
+
+/// The minor version of bevy targeted, as selected by feature
+fn bevy_minor() -> u32 {
+    if cfg!(feature = "bevy_0_16") {
+        16
+    } else if cfg!(feature = "bevy_0_15") {
+        15
+    } else if cfg!(feature = "bevy_0_14") {
+        14
+    } else {
+        13
+    }
+}
+
+/// The module exporting `Command`, which moved from `system` to `world` in bevy 0.14 and back in 0.16
+fn command_module() -> TokenStream {
+    if (14..16).contains(&bevy_minor()) {
+        quote!(world)
+    } else {
+        quote!(system)
+    }
+}
+
+/// The module exporting `CommandQueue`, which moved from `system` to `world` in bevy 0.14
+fn command_queue_module() -> TokenStream {
+    if bevy_minor() >= 14 {
+        quote!(world)
+    } else {
+        quote!(system)
+    }
+}
+
+/// The `Resource` trait, which moved to its own module in bevy 0.16
+fn resource_trait(ecs_root: &Path) -> TokenStream {
+    if bevy_minor() >= 16 {
+        quote!(#ecs_root ::resource::Resource)
+    } else {
+        quote!(#ecs_root ::system::Resource)
+    }
+}
+
+/// The `FrameCount` resource, which moved from `bevy_core` to `bevy_diagnostic` in bevy 0.16
+fn frame_count(ecs_root: &Path) -> TokenStream {
+    let krate = if bevy_minor() >= 16 {
+        sibling_crate(ecs_root, "diagnostic")
+    } else {
+        sibling_crate(ecs_root, "core")
+    };
+    quote!(#krate ::FrameCount)
+}
+
+/// The module exporting `Children`, which moved from `bevy_hierarchy` into `bevy_ecs` in bevy 0.16
+fn hierarchy_module(ecs_root: &Path) -> TokenStream {
+    if bevy_minor() >= 16 {
+        quote!(#ecs_root ::hierarchy)
+    } else {
+        let hierarchy = sibling_crate(ecs_root, "hierarchy");
+        quote!(#hierarchy)
+    }
+}
+
+/// The `Duration` and `Instant` types, taken from `core` and `bevy_platform` in bevy 0.16 rather than `bevy_utils`
+fn time_types(ecs_root: &Path) -> (TokenStream, TokenStream) {
+    if bevy_minor() >= 16 {
+        let platform = sibling_crate(ecs_root, "platform");
+        (
+            quote!(::core::time::Duration),
+            quote!(#platform ::time::Instant),
+        )
+    } else {
+        let utils = sibling_crate(ecs_root, "utils");
+        (quote!(#utils ::Duration), quote!(#utils ::Instant))
+    }
+}
+
+/// The module exporting `States`, `OnEnter` and `OnExit`, which moved to `bevy_state` in bevy 0.14
+fn state_module(ecs_root: &Path) -> TokenStream {
+    if bevy_minor() >= 14 {
+        let state = sibling_crate(ecs_root, "state");
+        quote!(#state ::state)
+    } else {
+        quote!(#ecs_root ::schedule)
+    }
+}
+
+/// The world of a `SubApp`, which wraps an `App` before bevy 0.14
+fn sub_app_world(sub_app: TokenStream, mutable: bool) -> TokenStream {
+    match (bevy_minor() >= 14, mutable) {
+        (true, true) => quote!(#sub_app.world_mut()),
+        (true, false) => quote!(#sub_app.world()),
+        (false, true) => quote!(&mut #sub_app.app.world),
+        (false, false) => quote!(&#sub_app.app.world),
+    }
+}
+
+/// The params of an entity command taking `entity` and `world`, along with statements binding them
+/// Entity commands are handed an `EntityWorldMut` from bevy 0.16, so both are bound from that instead
+fn entity_command_params(
+    entity: impl ToTokens,
+    world: impl ToTokens,
+    ecs_root: &Path,
+) -> (TokenStream, TokenStream) {
+    if bevy_minor() >= 16 {
+        (
+            quote!(__target: #ecs_root ::world::EntityWorldMut<'_>),
+            quote!(
+                let #entity = __target.id();
+                let #world = __target.into_world_mut();
+            ),
+        )
+    } else {
+        (quote!(#entity, #world), quote!())
+    }
+}
+
+/// Calls `apply` with an entity command for `entity`, which takes an `EntityWorldMut` from bevy 0.16
+fn apply_entity_command(
+    apply: impl ToTokens,
+    command: impl ToTokens,
+    entity: impl ToTokens,
+    world: impl ToTokens,
+) -> TokenStream {
+    if bevy_minor() >= 16 {
+        quote!(#apply (#command, #world.entity_mut(#entity)))
+    } else {
+        quote!(#apply (#command, #entity, #world))
+    }
+}
+
+/// The method queueing commands on `Commands` and `EntityCommands`, renamed from `add` in bevy 0.15
+fn queue_method() -> TokenStream {
+    if bevy_minor() >= 15 {
+        quote!(queue)
+    } else {
+        quote!(add)
+    }
+}
+
+/// Calls `run_system_once_with`, which takes the system before its input from bevy 0.16
+fn run_once_with(input: impl ToTokens, system: impl ToTokens) -> TokenStream {
+    if bevy_minor() >= 16 {
+        quote!(run_system_once_with(#system, #input))
+    } else {
+        quote!(run_system_once_with(#input, #system))
+    }
+}
+
+/// Unwraps the `Result` returned by `run_system_once` from bevy 0.15, whose errors earlier versions panicked with
+fn run_once_result() -> TokenStream {
+    if bevy_minor() >= 15 {
+        quote!(.unwrap())
+    } else {
+        quote!()
+    }
+}
+
+/// The `In` of a system taking `ty`, which is wrapped in `In` from bevy 0.15
+fn system_input(ty: TokenStream, ecs_root: &Path) -> TokenStream {
+    if bevy_minor() >= 15 && ty.to_string() != "()" {
+        quote!(#ecs_root ::system::In<#ty>)
+    } else {
+        ty
+    }
+}
+
+/// An `# Arguments` section listing each documented param, or nothing when none are
+fn arguments_doc<'a>(
+    params: impl Iterator<Item = (&'a TokenStream, &'a Option<String>)>,
+) -> TokenStream {
+    let lines = params
+        .filter_map(|(name, doc)| {
+            let doc = doc.as_ref()?.replace('\n', " ");
+            Some(format!(" * `{name}` - {doc}"))
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        quote!()
+    } else {
+        quote!(
+            #[doc = ""]
+            #[doc = " # Arguments"]
+            #[doc = ""]
+            #(#[doc = #lines])*
+        )
+    }
+}
+
+/// An `# Example` section showing how our method is called
+fn example_doc(lines: &[String]) -> TokenStream {
+    if lines.is_empty() {
+        return quote!();
+    }
+    let lines = lines.iter().map(|line| format!(" {line}"));
+    quote!(
+        #[doc = ""]
+        #[doc = " # Example"]
+        #[doc = ""]
+        #[doc = " ```ignore"]
+        #(#[doc = #lines])*
+        #[doc = " ```"]
+    )
+}
+
+/// A type as written, since tokens print with spaces between each of them
+/// Only spaces separating two words are kept, such as in `&'static str`
+fn type_name(ty: &str) -> String {
+    let word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut name = String::new();
+    let mut space = false;
+    for c in ty.trim().chars() {
+        if c == ' ' {
+            space = true;
+            continue;
+        }
+        if space && name.ends_with(word) && word(c) {
+            name.push(' ');
+        }
+        space = false;
+        name.push(c);
+    }
+    name
+}
+
+/// one of bevy's other crates, found alongside the ecs root
+fn sibling_crate(ecs_root: &Path, name: &str) -> Path {
+    let mut path = ecs_root.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = if last.ident == "bevy_ecs" {
+            Ident::new(&format!("bevy_{name}"), last.ident.span())
+        } else {
+            Ident::new(name, last.ident.span())
+        };
+    }
+    path
+}
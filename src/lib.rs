@@ -5,21 +5,108 @@ use gen::commandify;
 
 use proc_macro::TokenStream as ProcTokenStream;
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Error, ItemFn, Meta};
+use syn::{parse_macro_input, Error, ItemFn, ItemTrait, Meta};
+
+// `bevy_0_13` is a default feature, so targeting a later bevy takes eg. `--no-default-features --features bevy_0_16`,
+// or `default-features = false, features = ["bevy_0_16"]` as a dependency. Enabling more than one is an error
+#[cfg(any(
+    all(
+        feature = "bevy_0_13",
+        any(feature = "bevy_0_14", feature = "bevy_0_15", feature = "bevy_0_16")
+    ),
+    all(
+        feature = "bevy_0_14",
+        any(feature = "bevy_0_15", feature = "bevy_0_16")
+    ),
+    all(feature = "bevy_0_15", feature = "bevy_0_16"),
+))]
+compile_error!("features `bevy_0_13` to `bevy_0_16` are mutually exclusive, use eg. `--no-default-features --features bevy_0_16` to target a later bevy");
+
+#[cfg(not(any(
+    feature = "bevy_0_13",
+    feature = "bevy_0_14",
+    feature = "bevy_0_15",
+    feature = "bevy_0_16"
+)))]
+compile_error!("one of the features `bevy_0_13` to `bevy_0_16` must be enabled");
 
 /// Promotes a function to a `Command` struct, and creates an equivalent `Commands` and `World` method via trait extensions
 ///
 /// - `#[command(no_trait)]` prevents generating a trait method for `Commands`
 /// - `#[command(no_world)]` prevents generating a trait impl for `World`
 /// - `#[command(name = T)]` will use this name for the method and related struct/trait names
+/// - `#[command(method = T)]` will use this name for the generated method only, without affecting struct/trait names
 /// - `#[command(struct_name = T)]` will use this name for the generated struct, defaults to `<Foo>Command`
 /// - `#[command(trait_name = T)]` will use this name for the generated trait, defaults to `Commands<Foo>Ext`
+/// - `#[command(read_only)]` calls a function taking `&World` immediately via `World<Foo>Ext`, returning its value, rather than generating a `Command`. The method is `#[must_use]`, or uses the fn's own `#[must_use = ".."]`
+/// - `#[command(no_struct)]` skips generating the struct, implementing `Command` for an existing struct named by `struct_name` instead. Its fields must be named after the params
+/// - `#[command(closure)]` queues the body as a closure instead of generating a `Command` struct, so only the trait methods are generated
+/// - `#[command(test)]` generates a `#[cfg(test)]` smoke test applying the command to an empty world, with each field set to its `Default`. Buffered commands are drained by the test too
+/// - `#[command(arbitrary)]` derives `arbitrary::Arbitrary` on the generated struct, or only with the caller's feature `F` enabled via `arbitrary = "F"`
+/// - `#[command(check = F)]` also generates `try_<foo>`, which calls `F` with references to the fields and only calls the method if it returns `Ok`. Use `check_error = T` to set its error type, defaults to `String`
+/// - `#[command(variants(a = (x, y), b = (z, w)))]` also generates `<foo>_a` and `<foo>_b`, which call the method with those values for its fields
+/// - `#[command(max_per_frame = N)]` drops applications beyond `N` per frame, as counted by `FrameCount`, and doesn't limit them without it. Use `on_overflow = F` to call `F(command, world)` with them instead
+/// - `#[command(cached)]` keeps the system behind a non-exclusive command initialized between applications, rather than rebuilding it each time. Its `Local`s persist as a result, with generic commands keeping one system per instantiation
+/// - `#[command(budget_ms = N)]` buffers the command when issued via any generated method, generating a `<struct_name>_drain` system which applies them until `N`ms have passed
+/// - `#[command(apply_in_fixed)]` buffers the command when issued via any generated method, generating a `<Foo>FixedPlugin` which applies them all at the start of each `FixedUpdate`
+/// - `#[command(scoped_to_state = S)]` with `budget_ms` or `apply_in_fixed` generates a `<Foo>StateScopePlugin`, which drops any buffered commands when exiting state `S`
+/// - `#[command(example_docs)]` appends an example to the method docs, showing how it's called
+/// - `#[command(doc_hidden)]` marks the generated structs `#[doc(hidden)]`, or with `doc_hidden = all` the generated traits too
+/// - `#[command(sealed)]` seals the generated traits behind a private supertrait, preventing other implementations
+/// - `#[command(world_trait_name = T)]` will implement the `World` method on its own trait named `T`, which may exist without the other trait
+/// - `#[command(trait_vis = V)]` and `#[command(world_trait_vis = V)]` change the visibility of the generated traits, defaults to `pub`
+/// - `#[command(no_original)]` hides the emitted fn inside an anonymous `const _` block, so that only the generated items are visible and its name is free for other items
+/// - `#[command(fn_vis = V)]` changes the visibility of the emitted fn only, eg. `fn_vis = ""` keeps it private while the generated items share its written visibility
+/// - `#[command(also_impl = T)]` also implements the generated trait for `T` by forwarding to its `DerefMut` target, may be repeated
+/// - `#[command(sub_app)]` also implements the `World` method for `SubApp` by forwarding to its world. Use `sub_app = T` to change the root of `SubApp`, defaults to `bevy::app`
+/// - `#[command(world_impl = T)]` also implements the `World` method for `T` by forwarding to its `AsMut<World>` target, may be repeated
+/// - `#[command(app)]` generates `App<Foo>Ext` with a `queue_<foo>` method, applying the command once during `Startup`. Use `app = T` to change the root of `App`, defaults to `bevy::app`
+/// - `#[command(run_on_enter)]` and `#[command(run_on_exit)]` add `queue_<foo>_on_enter` and `queue_<foo>_on_exit` to `App<Foo>Ext`, queueing the command each time the given state is entered or exited. Fields must be `Clone`, implies `app`
+/// - `#[command(hotkey = K)]` generates a `<Foo>HotkeyPlugin`, which queues the command with `Default` arguments when key `K` is pressed. `K` may be an array of keys forming a chord
+/// - `#[command(constructor)]` also generates a free function `<foo>_command` taking the fields and returning `impl Command`, or named `T` with `constructor = T`
+/// - `#[command(into_system)]` generates `into_system` on the struct, returning a system which applies a clone of the command on each run
 /// - `#[command(ecs = T)]` to change the crate root to T, defaults to `bevy::ecs`
 /// - `#[command(bevy_ecs)]` to change the crate root to `bevy_ecs`
 ///
 /// Note: `T`s may be optionally quoted
 ///
-/// Commands may optionally return `&mut Self` to allow chaining their calls
+/// The generated struct has a `NAME` constant, holding the command's name as given by the fn or `name`
+///
+/// It also has `DOC`, `FIELDS`, `FILE` and `LINE` constants, holding the first paragraph of the fn's docs, each field's name and type, and where the fn was declared
+///
+/// Structs without fields also have an `INSTANCE` constant, holding their only value
+///
+/// Its `access` function returns the components and resources the command reads and writes, with exclusive commands having access to everything
+///
+/// Commands may optionally return `&mut Self` to allow chaining their calls. These methods aren't `#[must_use]`, as a chain ends by dropping the returned `&mut Self`
+///
+/// Defaults on generic params carry over to the generated struct, while the fn and trait methods take the generics without them
+///
+/// Destructured params become fields named after their type in snake case, or `arg<index>` when the type has no plain name or it is taken
+///
+/// The same goes for destructured inputs within `In`, eg. `In(Request { pos, .. }): In<Request>` takes a `request` field
+///
+/// Underscored params become fields without their leading underscores, eg. `_unused` becomes `unused`, unless that name is taken. A `_` param is named after its type like other patterns
+///
+/// Lint attributes such as `#[allow(..)]` and `#[expect(..)]` carry over to the generated items
+///
+/// Commands may be declared from within `macro_rules!`, as the generated items are named after the fn and take the spans of its params
+///
+/// Commands in one module generating the same items collide as duplicate definitions, rename one with `name`, `struct_name` or `trait_name`. Commands with different traits may share a method, called through their trait
+///
+/// Args which have no effect alongside others, eg. `trait_name` with `no_trait`, are warned about as deprecations
+///
+/// Doc comments on params, or `#[arg(doc = "..")]`, document the generated struct fields and are listed in the method docs
+///
+/// Other attributes on params, such as `#[serde(..)]`, move onto the generated struct fields. Lint attributes stay on the fn as well, while `cfg`s only apply to the fn
+///
+/// Exclusive commands may also take `Local<T>`, `&mut QueryState<D, F>` or `&mut SystemState<P>` params. Their state does not persist between applications
+///
+/// Exclusive commands may also take a `Commands` param, which is applied at the end of the body. The world may only be read while it's in use
+///
+/// The generated struct has an `apply_batch` function, applying many commands while initializing their system params only once
+///
+/// Commands taking an `Option<Entity>` also get an `EntityCommands` and `EntityWorldMut` method via `EntityCommands<Foo>Ext`, which passes `Some` of that entity
 #[proc_macro_attribute]
 pub fn command(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
     let args = parse_macro_input!(args with Punctuated::<Meta, syn::Token![,]>::parse_terminated);
@@ -34,14 +121,84 @@ pub fn command(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream
 ///
 /// - `#[entity_command(no_trait)]` prevents generating a trait method for EntityCommands
 /// - `#[entity_command(name = T)]` will use this name for the method and related struct/trait names
+/// - `#[entity_command(method = T)]` will use this name for the generated method only, without affecting struct/trait names
 /// - `#[entity_command(struct_name = T)]` will use this name for the generated struct, defaults to `<Foo>EntityCommand`
 /// - `#[entity_command(trait_name = T)]` will use this name for the generated trait, defaults to `EntityCommands<Foo>Ext`
+/// - `#[entity_command(commands)]` also generates a `Commands` and `World` method taking the target entity explicitly, via `Commands<Foo>Ext`
+/// - `#[entity_command(all)]` also generates a `Commands` and `World` method `<foo>_all` taking many target entities, via `Commands<Foo>Ext`. Fields must be `Clone`
+/// - `#[entity_command(both)]` also generates a plain `<Foo>Command` struct taking the target entity as a field. Implies `commands`
+/// - `#[entity_command(children)]` and `#[entity_command(descendants)]` also generate `<foo>_children` and `<foo>_descendants`, applying the command to each of the target's children or descendants as found when applied. Fields must be `Clone`
+/// - `#[entity_command(spawns)]` also generates a `Commands` and `World` method spawning the target entity, via `Commands<Foo>Ext`. It returns the entity's `EntityCommands` or `EntityWorldMut` for further chaining
+/// - `#[entity_command(no_struct)]` skips generating the struct, implementing `EntityCommand` for an existing struct named by `struct_name` instead. Its fields must be named after the params
+/// - `#[entity_command(closure)]` queues the body as a closure instead of generating a `EntityCommand` struct, so only the trait methods are generated
+/// - `#[entity_command(test)]` generates a `#[cfg(test)]` smoke test applying the command to an empty world, with each field set to its `Default`. Buffered commands are drained by the test too
+/// - `#[entity_command(arbitrary)]` derives `arbitrary::Arbitrary` on the generated struct, or only with the caller's feature `F` enabled via `arbitrary = "F"`
+/// - `#[entity_command(check = F)]` also generates `try_<foo>`, which calls `F` with references to the fields and only calls the method if it returns `Ok`. Use `check_error = T` to set its error type, defaults to `String`
+/// - `#[entity_command(variants(a = (x, y), b = (z, w)))]` also generates `<foo>_a` and `<foo>_b`, which call the method with those values for its fields
+/// - `#[entity_command(max_per_frame = N)]` drops applications beyond `N` per frame, as counted by `FrameCount`, and doesn't limit them without it. Use `on_overflow = F` to call `F(command, entity, world)` with them instead
+/// - `#[entity_command(cached)]` keeps the system behind a non-exclusive command initialized between applications, rather than rebuilding it each time. Its `Local`s persist as a result, with generic commands keeping one system per instantiation
+/// - `#[entity_command(budget_ms = N)]` buffers the command when issued via any generated method, generating a `<struct_name>_drain` system which applies them until `N`ms have passed
+/// - `#[entity_command(apply_in_fixed)]` buffers the command when issued via any generated method, generating a `<Foo>FixedPlugin` which applies them all at the start of each `FixedUpdate`
+/// - `#[entity_command(scoped_to_state = S)]` with `budget_ms` or `apply_in_fixed` generates a `<Foo>StateScopePlugin`, which drops any buffered commands when exiting state `S`
+/// - `#[entity_command(cancel_on_despawn)]` with `budget_ms` or `apply_in_fixed` drops buffered commands whose target has despawned, rather than applying them
+/// - `#[entity_command(example_docs)]` appends an example to the method docs, showing how it's called
+/// - `#[entity_command(doc_hidden)]` marks the generated structs `#[doc(hidden)]`, or with `doc_hidden = all` the generated traits too
+/// - `#[entity_command(sealed)]` seals the generated traits behind a private supertrait, preventing other implementations
+/// - `#[entity_command(world_trait_name = T)]` will implement the `EntityWorldMut` method on its own trait named `T`, which may exist without the other trait
+/// - `#[entity_command(trait_vis = V)]` and `#[entity_command(world_trait_vis = V)]` change the visibility of the generated traits, defaults to `pub`
+/// - `#[entity_command(no_original)]` hides the emitted fn inside an anonymous `const _` block, so that only the generated items are visible and its name is free for other items
+/// - `#[entity_command(fn_vis = V)]` changes the visibility of the emitted fn only, eg. `fn_vis = ""` keeps it private while the generated items share its written visibility
+/// - `#[entity_command(also_impl = T)]` also implements the generated trait for `T` by forwarding to its `DerefMut` target, may be repeated
+/// - `#[entity_command(constructor)]` also generates a free function `<foo>_entity_command` taking the fields and returning `impl EntityCommand`, or named `T` with `constructor = T`
+/// - `#[entity_command(into_system)]` generates `into_system` on the struct, returning a system which applies a clone of the command to its `Entity` input on each run
 /// - `#[entity_command(ecs = T)]` to change the crate root to T, defaults to `bevy::ecs`
 /// - `#[entity_command(bevy_ecs)]` to change the crate root to `bevy_ecs`
 ///
 /// Note: `T`s may be optionally quoted
 ///
-/// Commands may optionally return `&mut Self` to allow chaining their calls
+/// The generated struct has a `NAME` constant, holding the command's name as given by the fn or `name`
+///
+/// It also has `DOC`, `FIELDS`, `FILE` and `LINE` constants, holding the first paragraph of the fn's docs, each field's name and type, and where the fn was declared
+///
+/// Structs without fields also have an `INSTANCE` constant, holding their only value
+///
+/// Its `access` function returns the components and resources the command reads and writes, with exclusive commands having access to everything
+///
+/// Commands may optionally return `&mut Self` to allow chaining their calls. These methods aren't `#[must_use]`, as a chain ends by dropping the returned `&mut Self`
+///
+/// Defaults on generic params carry over to the generated struct, while the fn and trait methods take the generics without them
+///
+/// Destructured params become fields named after their type in snake case, or `arg<index>` when the type has no plain name or it is taken
+///
+/// The same goes for destructured inputs within `In`, eg. `In(Request { pos, .. }): In<Request>` takes a `request` field
+///
+/// Underscored params become fields without their leading underscores, eg. `_unused` becomes `unused`, unless that name is taken. A `_` param is named after its type like other patterns
+///
+/// Lint attributes such as `#[allow(..)]` and `#[expect(..)]` carry over to the generated items
+///
+/// Commands may be declared from within `macro_rules!`, as the generated items are named after the fn and take the spans of its params
+///
+/// Commands in one module generating the same items collide as duplicate definitions, rename one with `name`, `struct_name` or `trait_name`. Commands with different traits may share a method, called through their trait
+///
+/// Args which have no effect alongside others, eg. `trait_name` with `no_trait`, are warned about as deprecations
+///
+/// Doc comments on params, or `#[arg(doc = "..")]`, document the generated struct fields and are listed in the method docs
+///
+/// Other attributes on params, such as `#[serde(..)]`, move onto the generated struct fields. Lint attributes stay on the fn as well, while `cfg`s only apply to the fn
+///
+/// Parameters of type `Item<&C>` or `Item<&mut C>` are fetched from the target entity when the command is applied, leaving targets without `C` untouched. `Item` is only a marker for the macro, the fn receives `&C` or `&mut C`
+///
+/// Exclusive commands may also take `Local<T>`, `&mut QueryState<D, F>` or `&mut SystemState<P>` params. Their state does not persist between applications
+///
+/// Exclusive commands may also take a `Commands` param, which is applied at the end of the body. The world may only be read while it's in use
+///
+/// Alternatively, an `EntityWorldMut` parameter may be taken in place of the `Entity`
+///
+/// When taking several entities, the first is the target unless another is marked `#[target]`. The others become regular fields
+///
+/// The generated struct has a `const` `new` constructor taking its fields, and `with_entity` binds it to an entity as a plain `Command`
+///
+/// The generated struct has an `apply_batch` function, applying many `(Entity, Self)` pairs while initializing their system params only once
 #[proc_macro_attribute]
 pub fn entity_command(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
     let args = parse_macro_input!(args with Punctuated::<Meta, syn::Token![,]>::parse_terminated);
@@ -51,3 +208,53 @@ pub fn entity_command(args: ProcTokenStream, input: ProcTokenStream) -> ProcToke
         .unwrap_or_else(Error::into_compile_error)
         .into()
 }
+
+/// Generates a command from each method of a trait, calling the implementation of the type given by `delegate`
+///
+/// eg. `#[commandify_trait(delegate = Combat)]` on `trait CombatApi { fn attack(world: &mut World, damage: u32); }` generates `AttackCommand` and `CommandsAttackExt`, calling `<Combat as CombatApi>::attack`
+///
+/// Methods are commands by default, `#[entity_command]` marks them as entity commands instead. Either may take args as usual, while args given to `commandify_trait` apply to every method
+///
+/// Methods cannot take `self`, and each also becomes a free fn of the same name
+#[proc_macro_attribute]
+pub fn commandify_trait(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
+    let args = parse_macro_input!(args with Punctuated::<Meta, syn::Token![,]>::parse_terminated);
+    let item = parse_macro_input!(input as ItemTrait);
+
+    gen::commandify_trait(args, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generates a command applying several other commands in order, within the one flush
+///
+/// eg. `#[command_sequence]` on `fn ambush(pos: Vec2, leader: Entity) { spawn_enemy(pos, 3); leader.rally(10); }` generates `AmbushCommand` and `CommandsAmbushExt`
+///
+/// Each step calls a command by its method, with args mapped from the sequence's params. Entity commands are called on the entity they apply to
+///
+/// Steps are applied through the commands' `World` methods, which must be in scope, so cannot be commands with `no_world`
+///
+/// Args are those of `command`, as the sequence is an exclusive command taking its params as fields. Its world is named through the `ecs` root, so `World` need not be in scope
+#[proc_macro_attribute]
+pub fn command_sequence(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
+    let args = parse_macro_input!(args with Punctuated::<Meta, syn::Token![,]>::parse_terminated);
+    let item = parse_macro_input!(input as ItemFn);
+
+    gen::command_sequence(args, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Re-exports the extension traits of several commands from one module, so that a feature area needs a single import
+///
+/// eg. `command_group!(pub mod combat { CommandsAttackExt, EntityCommandsHealExt })`, then `use combat::*;`
+///
+/// Paths are relative to where the group is declared
+///
+/// Paths are written as in a `use`, so whole modules of commands may be globbed into a prelude, eg. `command_group!(pub mod prelude { combat::*, magic::{*, CommandsFireballExt} })`
+#[proc_macro]
+pub fn command_group(input: ProcTokenStream) -> ProcTokenStream {
+    let group = parse_macro_input!(input as parse::CommandGroup);
+
+    gen::command_group(group).into()
+}
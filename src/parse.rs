@@ -1,29 +1,169 @@
-use proc_macro2::{Ident, TokenStream};
+use inflector::Inflector;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
 use syn::{
-    parse_quote, Attribute, Error, Expr, ExprLit, FnArg, GenericArgument, Lit, Meta, MetaNameValue,
-    Pat, Path, PathArguments, ReturnType, Type,
+    braced, parse_quote, parse_quote_spanned, Attribute, Error, Expr, ExprLit, FnArg,
+    GenericArgument, GenericParam, Generics, Lit, LitInt, LitStr, Meta, MetaNameValue, Pat,
+    PatIdent, PatType, Path, PathArguments, ReturnType, Type, TypeReference, UseTree, Visibility,
 };
 
+/// which generated items to hide from docs
+#[derive(PartialEq)]
+pub enum DocHidden {
+    Structs,
+    All,
+}
+
+/// whether to derive `Arbitrary`, and under which feature
+pub enum Arbitrary {
+    Always(Span),
+    Feature(LitStr),
+}
+
+impl Arbitrary {
+    pub fn span(&self) -> Span {
+        match self {
+            Arbitrary::Always(span) => *span,
+            Arbitrary::Feature(feature) => feature.span(),
+        }
+    }
+}
+
+/// what to name the free function returning our command
+pub enum Constructor {
+    Default(Span),
+    Named(Ident),
+}
+
+impl Constructor {
+    pub fn span(&self) -> Span {
+        match self {
+            Constructor::Default(span) => *span,
+            Constructor::Named(ident) => ident.span(),
+        }
+    }
+}
+
 pub struct MacroArgs {
     pub no_trait: bool,
     pub no_world: bool,
+    pub commands: Option<Span>,
+    pub all: Option<Span>,
+    pub both: Option<Span>,
+    /// generates a `Commands` method spawning our target, returning it for further chaining
+    pub spawns: Option<Span>,
+    /// generates methods applying our entity command to the target's children or descendants
+    pub children: Option<Span>,
+    pub descendants: Option<Span>,
+    pub closure: Option<Span>,
+    pub no_struct: Option<Span>,
+    pub example_docs: bool,
+    pub test: Option<Span>,
+    pub arbitrary: Option<Arbitrary>,
+    /// a fn validating our fields before queueing, and the error it returns
+    pub check: Option<Path>,
+    pub check_error: Option<Type>,
+    pub variants: Vec<Variant>,
+    /// how many times our command may apply per frame, and what to do with the rest
+    pub max_per_frame: Option<LitInt>,
+    pub on_overflow: Option<Path>,
+    /// milliseconds per run of our drain system, when buffering our command
+    pub budget_ms: Option<Lit>,
+    /// buffers our command to be applied at the start of each fixed timestep
+    pub apply_in_fixed: Option<Span>,
+    /// the state whose exit drops any of our commands still buffered
+    pub scoped_to_state: Option<Expr>,
+    /// whether buffered entity commands are dropped when their target despawns, rather than applied
+    pub cancel_on_despawn: Option<Span>,
+    /// keeps our system initialized between applications rather than rebuilding it each time
+    pub cached: Option<Span>,
+    /// generates a method converting our struct into a system which applies it on each run
+    pub into_system: Option<Span>,
+    /// hides the emitted fn in an anonymous block, alongside the impls calling it
+    pub no_original: Option<Span>,
+    /// the key, or chord of keys, queueing our command from a generated plugin
+    pub hotkey: Option<Expr>,
+    pub sealed: bool,
+    pub also_impl: Vec<Type>,
+    pub read_only: Option<Span>,
+    /// the root of `App`, when generating a startup method for it
+    pub app: Option<Path>,
+    /// generates `App` methods queueing our command on entering or exiting a state
+    pub run_on_enter: Option<Span>,
+    pub run_on_exit: Option<Span>,
+    /// the root of `SubApp`, when implementing the world trait for it
+    pub sub_app: Option<Path>,
+    pub world_impl: Vec<Type>,
+    pub constructor: Option<Constructor>,
     pub name: Ident,
+    pub method: Option<Ident>,
+    pub doc_hidden: Option<DocHidden>,
     pub struct_name: Option<Ident>,
     pub trait_name: Option<Ident>,
+    pub world_trait_name: Option<Ident>,
+    pub trait_vis: Option<Visibility>,
+    pub world_trait_vis: Option<Visibility>,
+    /// the visibility of the emitted fn, when it differs from our generated items
+    pub fn_vis: Option<Visibility>,
     pub ecs_root: Option<Path>,
 }
 
+/// a named preset of our fields, generating a method which queues our command with them
+pub struct Variant {
+    pub name: Ident,
+    pub values: Vec<Expr>,
+}
+
 /// parse macro args
 pub fn macro_args(args: &Punctuated<Meta, Comma>, mut name: Ident) -> Result<MacroArgs, Error> {
     // arguments
     let mut no_trait = false;
     let mut no_world = false;
+    let mut commands = None;
+    let mut all = None;
+    let mut both = None;
+    let mut spawns = None;
+    let mut children = None;
+    let mut descendants = None;
+    let mut closure = None;
+    let mut no_struct = None;
+    let mut example_docs = false;
+    let mut test = None;
+    let mut arbitrary = None;
+    let mut check = None;
+    let mut check_error = None;
+    let mut variants = Vec::new();
+    let mut max_per_frame = None;
+    let mut on_overflow = None;
+    let mut budget_ms = None;
+    let mut apply_in_fixed = None;
+    let mut scoped_to_state = None;
+    let mut cancel_on_despawn = None;
+    let mut cached = None;
+    let mut into_system = None;
+    let mut no_original = None;
+    let mut hotkey = None;
+    let mut sealed = false;
+    let mut also_impl = Vec::new();
+    let mut read_only = None;
+    let mut app = None;
+    let mut run_on_enter = None;
+    let mut run_on_exit = None;
+    let mut sub_app = None;
+    let mut world_impl = Vec::new();
+    let mut constructor = None;
+    let mut method = None;
+    let mut doc_hidden = None;
     let mut struct_name = None;
     let mut trait_name = None;
+    let mut world_trait_name = None;
+    let mut trait_vis = None;
+    let mut world_trait_vis = None;
+    let mut fn_vis = None;
     let mut ecs_root = None;
 
     // parse macro arguments
@@ -35,18 +175,199 @@ pub fn macro_args(args: &Punctuated<Meta, Comma>, mut name: Ident) -> Result<Mac
             Meta::Path(path) if path.is_ident("no_world") => {
                 no_world = true;
             }
+            Meta::Path(path) if path.is_ident("commands") => {
+                commands = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("all") => {
+                all = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("both") => {
+                both = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("spawns") => {
+                spawns = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("children") => {
+                children = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("descendants") => {
+                descendants = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("closure") => {
+                closure = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("no_struct") => {
+                no_struct = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("example_docs") => {
+                example_docs = true;
+            }
+            Meta::Path(path) if path.is_ident("cancel_on_despawn") => {
+                cancel_on_despawn = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("apply_in_fixed") => {
+                apply_in_fixed = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("no_original") => {
+                no_original = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("cached") => {
+                cached = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("into_system") => {
+                into_system = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("test") => {
+                test = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("arbitrary") => {
+                arbitrary = Some(Arbitrary::Always(path.span()));
+            }
+            Meta::Path(path) if path.is_ident("sealed") => {
+                sealed = true;
+            }
+            Meta::Path(path) if path.is_ident("read_only") => {
+                read_only = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("app") => {
+                app = Some(parse_quote_spanned!(path.span()=> ::bevy::app));
+            }
+            Meta::Path(path) if path.is_ident("run_on_enter") => {
+                run_on_enter = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("run_on_exit") => {
+                run_on_exit = Some(path.span());
+            }
+            Meta::Path(path) if path.is_ident("sub_app") => {
+                sub_app = Some(parse_quote_spanned!(path.span()=> ::bevy::app));
+            }
+            Meta::Path(path) if path.is_ident("constructor") => {
+                constructor = Some(Constructor::Default(path.span()));
+            }
             Meta::Path(path) if path.is_ident("bevy_ecs") => {
                 ecs_root = Some(parse_quote!(::bevy_ecs));
             }
             Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("name") => {
                 name = value.try_to_ident()?;
             }
+            Meta::Path(path) if path.is_ident("doc_hidden") => {
+                doc_hidden = Some(DocHidden::Structs);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("doc_hidden") => {
+                let value = value.try_to_ident()?;
+                if value != "all" {
+                    return Err(Error::new(
+                        value.span(),
+                        "Expected `doc_hidden` or `doc_hidden = all`",
+                    ));
+                }
+                doc_hidden = Some(DocHidden::All);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("method") => {
+                method = Some(value.try_to_ident()?);
+            }
             Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("struct_name") => {
                 struct_name = Some(value.try_to_ident()?);
             }
             Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("trait_name") => {
                 trait_name = Some(value.try_to_ident()?);
             }
+            Meta::NameValue(MetaNameValue { path, value, .. })
+                if path.is_ident("world_trait_name") =>
+            {
+                world_trait_name = Some(value.try_to_ident()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("fn_vis") => {
+                fn_vis = Some(value.try_to_vis()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("trait_vis") => {
+                trait_vis = Some(value.try_to_vis()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. })
+                if path.is_ident("world_trait_vis") =>
+            {
+                world_trait_vis = Some(value.try_to_vis()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("also_impl") => {
+                also_impl.push(value.try_to_type()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("app") => {
+                app = Some(value.try_to_path()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("arbitrary") => {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Str(feature),
+                    ..
+                }) = value
+                else {
+                    return Err(Error::new(
+                        value.span(),
+                        "Expected `arbitrary` or `arbitrary = \"feature\"`",
+                    ));
+                };
+                arbitrary = Some(Arbitrary::Feature(feature.clone()));
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("check") => {
+                check = Some(value.try_to_path()?);
+            }
+            Meta::List(list) if list.path.is_ident("variants") => {
+                let presets =
+                    list.parse_args_with(Punctuated::<MetaNameValue, Comma>::parse_terminated)?;
+                for MetaNameValue { path, value, .. } in presets {
+                    let name = path.require_ident()?.clone();
+                    // `(a, b)` holds a value per field, and `(a)` or `a` a single one
+                    let values = match value {
+                        Expr::Paren(paren) => vec![*paren.expr],
+                        Expr::Tuple(tuple) => tuple.elems.into_iter().collect(),
+                        value => vec![value],
+                    };
+                    variants.push(Variant { name, values });
+                }
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("check_error") => {
+                check_error = Some(value.try_to_type()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. })
+                if path.is_ident("max_per_frame") =>
+            {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Int(max), ..
+                }) = value
+                else {
+                    return Err(Error::new(value.span(), "Expected `max_per_frame = N`"));
+                };
+                max_per_frame = Some(max.clone());
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. })
+                if path.is_ident("scoped_to_state") =>
+            {
+                scoped_to_state = Some(value.clone());
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("hotkey") => {
+                hotkey = Some(value.clone());
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("budget_ms") => {
+                let Expr::Lit(ExprLit {
+                    lit: lit @ (Lit::Int(_) | Lit::Float(_)),
+                    ..
+                }) = value
+                else {
+                    return Err(Error::new(value.span(), "Expected `budget_ms = N`"));
+                };
+                budget_ms = Some(lit.clone());
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("on_overflow") => {
+                on_overflow = Some(value.try_to_path()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("constructor") => {
+                constructor = Some(Constructor::Named(value.try_to_ident()?));
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("world_impl") => {
+                world_impl.push(value.try_to_type()?);
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("sub_app") => {
+                sub_app = Some(value.try_to_path()?);
+            }
             Meta::NameValue(MetaNameValue { path, value, .. }) if path.is_ident("ecs") => {
                 ecs_root = Some(value.try_to_path()?);
             }
@@ -62,25 +383,125 @@ pub fn macro_args(args: &Punctuated<Meta, Comma>, mut name: Ident) -> Result<Mac
     Ok(MacroArgs {
         no_trait,
         no_world,
+        commands,
+        all,
+        both,
+        spawns,
+        children,
+        descendants,
+        closure,
+        no_struct,
+        example_docs,
+        test,
+        arbitrary,
+        check,
+        check_error,
+        variants,
+        max_per_frame,
+        on_overflow,
+        budget_ms,
+        apply_in_fixed,
+        scoped_to_state,
+        cancel_on_despawn,
+        cached,
+        into_system,
+        no_original,
+        hotkey,
+        sealed,
+        also_impl,
+        read_only,
+        app,
+        run_on_enter,
+        run_on_exit,
+        sub_app,
+        world_impl,
+        constructor,
         name,
+        method,
+        doc_hidden,
         struct_name,
         trait_name,
+        world_trait_name,
+        trait_vis,
+        world_trait_vis,
+        fn_vis,
         ecs_root,
     })
 }
 
+/// `pub mod combat { CommandsAttackExt, EntityCommandsHealExt }`
+pub struct CommandGroup {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub name: Ident,
+    pub traits: Punctuated<GroupItem, Comma>,
+}
+
+/// a path to re-export, written as in a `use`, eg. `combat::{CommandsAttackExt, magic::*}`
+pub struct GroupItem {
+    pub leading_colon: Option<syn::Token![::]>,
+    pub tree: UseTree,
+}
+
+impl Parse for GroupItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(GroupItem {
+            leading_colon: input.parse()?,
+            tree: input.parse()?,
+        })
+    }
+}
+
+impl Parse for CommandGroup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<syn::Token![mod]>()?;
+        let name = input.parse()?;
+        let content;
+        braced!(content in input);
+        let traits = content.parse_terminated(GroupItem::parse, Comma)?;
+
+        Ok(CommandGroup {
+            attrs,
+            vis,
+            name,
+            traits,
+        })
+    }
+}
+
 pub struct SysArgs {
     pub entity: Option<TokenStream>,
+    /// the name of the target entity, if it has one
+    pub target: Option<TokenStream>,
+    /// the name of the first `Option<Entity>` field of a regular command
+    pub optional_target: Option<TokenStream>,
     pub fields: Vec<TokenStream>,
+    /// doc comments on the params behind each field
+    pub field_docs: Vec<Option<String>>,
+    /// other attributes on the params behind each field, re-emitted on the field
+    pub field_attrs: Vec<Vec<TokenStream>>,
     pub def_field_names: Vec<TokenStream>,
     pub impl_field_names: Vec<TokenStream>,
+    /// how each field is destructured from our struct, either shorthand or `name: pattern`
+    pub field_pats: Vec<TokenStream>,
     pub args: SystemArgs,
 }
 
 pub enum SystemArgs {
     /// Exclusive commands always have one SystemParam: &mut World
     /// All other params are inherently inputs
-    Exclusive { world: TokenStream },
+    /// Entity commands may also take `&C` or `&mut C` items, which are fetched from the entity and imply the world
+    /// They may also take exclusive system params, such as `Local<T>` or `&mut QueryState<D>`
+    /// And a `Commands` param, queuing into a temporary queue applied at the end of the body
+    Exclusive {
+        world: TokenStream,
+        world_name: TokenStream,
+        fetch: TokenStream,
+        params: Vec<TokenStream>,
+        commands: Option<Box<Pat>>,
+    },
     /// System commands have multiple SystemParams
     /// All inputs must be packed into the `In<T>` struct
     /// eg. `In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>`
@@ -91,42 +512,113 @@ pub enum SystemArgs {
 }
 
 /// parse command args
-pub fn fn_args(inputs: &Punctuated<FnArg, Comma>, entity_command: bool) -> Result<SysArgs, Error> {
+pub fn fn_args(
+    inputs: &Punctuated<FnArg, Comma>,
+    entity_command: bool,
+    ecs_root: &Path,
+) -> Result<SysArgs, Error> {
     let mut exclusive_fields = Vec::<TokenStream>::new();
+    let mut exclusive_field_docs = Vec::<Option<String>>::new();
+    let mut exclusive_field_attrs = Vec::<Vec<TokenStream>>::new();
     let mut exclusive_def_field_names = Vec::<TokenStream>::new();
     let mut exclusive_impl_field_names = Vec::<TokenStream>::new();
+    let mut exclusive_field_pats = Vec::<TokenStream>::new();
     let mut system_fields = Vec::<TokenStream>::new();
+    let mut system_field_docs = Vec::<Option<String>>::new();
+    let mut system_field_attrs = Vec::<Vec<TokenStream>>::new();
     let mut system_def_field_names = Vec::<TokenStream>::new();
     let mut system_impl_field_names = Vec::<TokenStream>::new();
+    let mut system_field_pats = Vec::<TokenStream>::new();
     let mut systems_in = Vec::<TokenStream>::new();
     let mut entity_name = None;
     let mut world_field = None;
+    let mut world_name = None;
     let mut entity_field = None;
+    let mut exclusive_entity_name = None;
+    let mut item_pats = Vec::<TokenStream>::new();
+    let mut item_types = Vec::<TokenStream>::new();
+    let mut item_muts = Vec::<bool>::new();
+    let mut item_span = None;
+    let mut entity_world = None;
+    let mut system_optional_target = None;
+    let mut exclusive_optional_target = None;
+    let mut exclusive_params = Vec::<TokenStream>::new();
+    let mut nested_commands = None;
+    // spans of the params which later errors are about
+    let mut in_span = None;
+    let mut target_span = None;
 
+    let target = target_entity(inputs, entity_command)?;
+    // names already bound by params, which our renamed fields must not reuse
+    let mut taken = Vec::new();
     for input in inputs {
+        if let FnArg::Typed(pt) = input {
+            bound_names(&pt.pat, &mut taken);
+        }
+    }
+
+    for (index, input) in inputs.iter().enumerate() {
         match input {
             // `self` types smell of methods
             FnArg::Receiver(inner) => {
                 return Err(Error::new(inner.span(), "Commands cannot be methods"))
             }
             FnArg::Typed(pt) => {
-                let name = pt.pat.clone();
-                // handle `&World`, `Entity`, and `In<>` types specially
+                let docs = param_docs(&pt.attrs)?;
+                let attrs = field_attrs(&pt.attrs);
+                let pt = &without_helper_attrs(pt);
+                let is_target = target == Some(index);
+                // handle `&mut World`, `Entity`, and `In<>` types specially
                 // builds a list of all types in the various parts necessary for generation
+                // everything else, including `&World` and `ParamSet`s, is a regular system param
                 match pt.ty.as_ref() {
-                    Type::Reference(tr) => {
-                        if tr.elem.to_token_stream().to_string() == "World" {
-                            world_field = Some(quote!(#pt));
-                            continue;
-                        }
+                    ty if is_exclusive_param(ty) => {
+                        exclusive_params.push(quote!(#pt));
+                        continue;
+                    }
+                    Type::Reference(tr) if tr.mutability.is_some() && is_world(&tr.elem) => {
+                        world_field = Some(quote!(#pt));
+                        world_name = Some(pt.pat.to_token_stream());
+                        continue;
                     }
                     Type::Path(path) => {
                         if let Some(seg) = path.path.segments.last() {
                             let ident = &seg.ident;
-                            if entity_command && ident == "Entity" {
+                            if let Some(tr) = item_reference(&pt.ty) {
+                                // `Item<&C>` and `Item<&mut C>` are fetched from the target entity, and bound as the plain reference
+                                if !entity_command {
+                                    return Err(Error::new(
+                                        pt.span(),
+                                        "`Item` params are fetched from the target entity, so only apply to entity commands",
+                                    ));
+                                }
+                                let tr = tr?;
+                                let pat = &pt.pat;
+                                item_pats.push(quote!(#pat));
+                                item_types.push(tr.to_token_stream());
+                                item_muts.push(tr.mutability.is_some());
+                                item_span.get_or_insert(pt.span());
+                                continue;
+                            } else if is_target && ident == "EntityWorldMut" {
+                                // resolved from the target entity, the same as items
+                                entity_world = Some(pt.pat.clone());
+                                entity_field = Some(quote!(__entity: #ecs_root ::entity::Entity));
+                                exclusive_entity_name = Some(quote!(__entity));
+                                continue;
+                            } else if is_target {
+                                target_span = Some(pt.span());
                                 entity_field = Some(quote!(#pt));
+                                if let Pat::Ident(pat) = pt.pat.as_ref() {
+                                    let name = &pat.ident;
+                                    exclusive_entity_name = Some(quote!(#name));
+                                }
+                                continue;
+                            } else if ident == "Commands" {
+                                // exclusive commands get one scoped to their body, a regular system param otherwise
+                                nested_commands = Some(pt.pat.clone());
                                 continue;
                             } else if ident == "In" {
+                                in_span.get_or_insert(pt.span());
                                 // in this case we need to additionally parse the parameter name which may expand into more through destructuring
                                 // normally destructuring is not allowed in commands macros, but it's needed in this style to support more than one input arg
                                 // todo: support destructuring in regular command macros because I hate myself?
@@ -136,26 +628,16 @@ pub fn fn_args(inputs: &Punctuated<FnArg, Comma>, entity_command: bool) -> Resul
                                     return Err(Error::new(pt.span(), "Unsupported input type"));
                                 };
                                 // Parse inner names for elements of In<elem> or In<(elems,)>
+                                // other patterns, eg. `In(SpawnRequest { pos, .. })`, take the whole value and are named after its type
                                 for pat in pat.elems {
                                     match pat {
-                                        Pat::Ident(pat) => names.push(pat),
-                                        Pat::Tuple(pt) => {
-                                            for pat in pt.elems {
-                                                let Pat::Ident(pat) = pat else {
-                                                    return Err(Error::new(
-                                                        pat.span(),
-                                                        "Invalid path",
-                                                    ));
-                                                };
-                                                names.push(pat);
-                                            }
-                                        }
-                                        _ => return Err(Error::new(pat.span(), "Unknown input")),
+                                        Pat::Tuple(pt) => names.extend(pt.elems),
+                                        pat => names.push(pat),
                                     }
                                 }
 
                                 // Parse inner types of In<ty> or In<(tys,)>
-                                let mut args: Vec<TokenStream> = Vec::new();
+                                let mut args: Vec<Type> = Vec::new();
                                 match &seg.arguments {
                                     PathArguments::AngleBracketed(inner) => {
                                         for arg in &inner.args {
@@ -169,10 +651,13 @@ pub fn fn_args(inputs: &Punctuated<FnArg, Comma>, entity_command: bool) -> Resul
                                             match ty {
                                                 Type::Tuple(tt) => {
                                                     for ty in &tt.elems {
-                                                        args.push(ty.to_token_stream());
+                                                        args.push(ty.clone());
                                                     }
                                                 }
-                                                Type::Path(tp) => args.push(tp.to_token_stream()),
+                                                // fn pointers may be higher-ranked, eg. `for<'a> fn(&'a T)`
+                                                Type::Path(_) | Type::BareFn(_) => {
+                                                    args.push(ty.clone())
+                                                }
                                                 _ => {
                                                     return Err(Error::new(
                                                         arg.span(),
@@ -190,29 +675,65 @@ pub fn fn_args(inputs: &Punctuated<FnArg, Comma>, entity_command: bool) -> Resul
                                     }
                                 };
 
+                                // there's no way to document each name, so docs only apply to a single input
+                                if let (Some(_), true) = (&docs, names.len() > 1) {
+                                    return Err(Error::new(
+                                        pt.span(),
+                                        "Docs on `In` params only apply when it holds a single input, use a struct to document each",
+                                    ));
+                                }
+
                                 // 1:1 name:type mapping
                                 if names.len() == args.len() {
                                     for (pat, arg) in names.into_iter().zip(args) {
-                                        let name = &pat.ident;
-                                        if entity_command && arg.to_string() == "Entity" {
+                                        let (name, field_pat) = match &pat {
+                                            Pat::Ident(ident) => {
+                                                let name = field_name(&ident.ident, &taken);
+                                                let field_pat = field_pat(&name, ident);
+                                                (name, field_pat)
+                                            }
+                                            // the pattern stays on our fn, so our struct holds the value as is
+                                            _ => {
+                                                let name = pattern_field_name(&arg, index, &taken);
+                                                taken.push(name.to_string());
+                                                (name.clone(), quote!(#name))
+                                            }
+                                        };
+                                        let name = &name;
+                                        // the first entity is the target, others are regular inputs
+                                        if entity_command
+                                            && target.is_none()
+                                            && entity_name.is_none()
+                                            && arg.to_token_stream().to_string() == "Entity"
+                                        {
                                             entity_name = Some(quote!(#name));
                                             entity_field = Some(quote!(#name: #arg));
                                             systems_in.push(quote!(#name));
                                             continue;
                                         }
+                                        if !entity_command && is_optional_entity(&arg) {
+                                            system_optional_target.get_or_insert(quote!(#name));
+                                        }
                                         system_fields.push(quote!(#name: #arg));
+                                        system_field_docs.push(docs.clone());
+                                        system_field_attrs.push(attrs.clone());
                                         system_def_field_names.push(quote!(#name));
                                         system_impl_field_names.push(quote!(#pat));
+                                        system_field_pats.push(field_pat);
                                         systems_in.push(quote!(#name));
                                     }
                                 }
                                 // 1:many name:type mapping
-                                else if names.len() == 1 && !args.is_empty() {
-                                    let pat = names.first().unwrap();
-                                    let name = &pat.ident;
+                                else if let ([Pat::Ident(pat)], false) =
+                                    (names.as_slice(), args.is_empty())
+                                {
+                                    let name = &field_name(&pat.ident, &taken);
                                     system_fields.push(quote!(#name: (#(#args,)*)));
+                                    system_field_docs.push(docs);
+                                    system_field_attrs.push(attrs);
                                     system_def_field_names.push(quote!(#name));
                                     system_impl_field_names.push(quote!(#pat));
+                                    system_field_pats.push(field_pat(name, pat));
                                     systems_in.push(quote!(#name));
                                 } else {
                                     return Err(Error::new(
@@ -231,51 +752,425 @@ pub fn fn_args(inputs: &Punctuated<FnArg, Comma>, entity_command: bool) -> Resul
                 // these fields are not `&mut World`, `Entity`, nor `In`
                 // they only matter for exclusive systems, for normal systems these are the system parameters included by the root system
 
-                let Pat::Ident(pat) = *name.clone() else {
-                    return Err(Error::new(name.span(), "Invalid path"));
+                // destructured params become a field named after their type, and are destructured again when applied
+                let pat = &pt.pat;
+                let name = match pat.as_ref() {
+                    Pat::Ident(pat) => field_name(&pat.ident, &taken),
+                    _ => pattern_field_name(&pt.ty, index, &taken),
                 };
-                let name = &pat.ident;
                 let ty = &pt.ty;
 
+                if !entity_command && is_optional_entity(ty) {
+                    exclusive_optional_target.get_or_insert(quote!(#name));
+                }
                 exclusive_fields.push(quote!(#name: #ty));
+                exclusive_field_docs.push(docs);
+                exclusive_field_attrs.push(attrs);
                 exclusive_def_field_names.push(quote!(#name));
                 exclusive_impl_field_names.push(quote!(#pat));
+                exclusive_field_pats.push(match pat.as_ref() {
+                    Pat::Ident(pat) => field_pat(&name, pat),
+                    _ => quote!(#name: #pat),
+                });
             }
         }
     }
 
+    // fetching items needs the world, so they imply an exclusive command
+    let exclusive = world_field.is_some() || !item_pats.is_empty() || entity_world.is_some();
+    if let (Some(_), Some(span)) = (&entity_world, in_span) {
+        return Err(Error::new(
+            span,
+            "`EntityWorldMut` commands are exclusive and cannot take `In` params",
+        ));
+    }
+    if let Some(span) = item_span {
+        if !systems_in.is_empty() {
+            return Err(Error::new(
+                span,
+                "Entity items can only be fetched by exclusive commands, use a `Query` instead",
+            ));
+        }
+        if entity_world.is_some() {
+            return Err(Error::new(
+                span,
+                "Entity items cannot be fetched alongside `EntityWorldMut`, which already borrows the entity",
+            ));
+        }
+    }
+
     // figure these out late since some parts have different meanings depending on whether this is an exclusive or normal system
-    let (fields, def_field_names, impl_field_names) = if world_field.is_some() {
+    let (
+        fields,
+        field_docs,
+        field_attrs,
+        def_field_names,
+        impl_field_names,
+        field_pats,
+        optional_target,
+    ) = if exclusive {
         (
             exclusive_fields,
+            exclusive_field_docs,
+            exclusive_field_attrs,
             exclusive_def_field_names,
             exclusive_impl_field_names,
+            exclusive_field_pats,
+            exclusive_optional_target,
         )
     } else {
         (
             system_fields,
+            system_field_docs,
+            system_field_attrs,
             system_def_field_names,
             system_impl_field_names,
+            system_field_pats,
+            system_optional_target,
         )
     };
 
-    let args = match world_field {
-        Some(world) => SystemArgs::Exclusive { world },
-        None => SystemArgs::System {
+    let args = if exclusive {
+        let (world, world_name) = match (world_field, world_name) {
+            (Some(world), Some(name)) => (world, name),
+            _ => (
+                quote!(__world: &mut #ecs_root ::world::World),
+                quote!(__world),
+            ),
+        };
+
+        // query all items at once so that they may borrow mutably together
+        let fetch = match (item_span, exclusive_entity_name.clone()) {
+            (None, Some(entity)) => match entity_world {
+                Some(pat) => quote!(let #pat = #world_name.entity_mut(#entity);),
+                None => quote!(),
+            },
+            (None, None) => quote!(),
+            (Some(span), None) => {
+                return Err(Error::new(
+                    target_span.unwrap_or(span),
+                    "Entity items require the `Entity` parameter to be a plain name",
+                ))
+            }
+            (Some(_), Some(entity)) => {
+                let item_names = (0..item_pats.len())
+                    .map(|i| Ident::new(&format!("__item{i}"), Span::call_site()))
+                    .collect::<Vec<_>>();
+                let item_values = item_muts.iter().zip(&item_names).map(|(is_mut, name)| {
+                    if *is_mut {
+                        quote!(#name.into_inner())
+                    } else {
+                        quote!(#name)
+                    }
+                });
+                quote!(
+                    let mut __query = #world_name.query::<(#(#item_types,)*)>();
+                    // targets missing any of the items are left alone, the same as a query which doesn't match
+                    let Ok((#(#item_names,)*)) = __query.get_mut(#world_name, #entity) else {
+                        return;
+                    };
+                    #(let #item_pats: #item_types = #item_values;)*
+                )
+            }
+        };
+
+        SystemArgs::Exclusive {
+            world,
+            world_name,
+            fetch,
+            params: exclusive_params,
+            commands: nested_commands,
+        }
+    } else {
+        SystemArgs::System {
             entity_name,
             systems_in,
-        },
+        }
+    };
+
+    let target = match &args {
+        SystemArgs::Exclusive { .. } => exclusive_entity_name,
+        SystemArgs::System { entity_name, .. } => entity_name.clone(),
     };
 
     Ok(SysArgs {
         entity: entity_field,
+        target,
+        optional_target,
         fields,
+        field_docs,
+        field_attrs,
         def_field_names,
         impl_field_names,
+        field_pats,
         args,
     })
 }
 
+/// underscored params become fields without the leading underscores, eg. `_unused` becomes `unused`
+/// keeping the original name if that's taken by another param, or nothing is left once stripped
+fn field_name(ident: &Ident, taken: &[String]) -> Ident {
+    let original = ident.to_string();
+    let name = original.trim_start_matches('_');
+    if name.len() == original.len()
+        || taken.iter().any(|other| other == name)
+        || syn::parse_str::<Ident>(name).is_err()
+    {
+        return ident.clone();
+    }
+    Ident::new(name, ident.span())
+}
+
+/// destructures a field into its param's binding, shorthand when the names match
+fn field_pat(name: &Ident, pat: &PatIdent) -> TokenStream {
+    if &pat.ident == name {
+        quote!(#pat)
+    } else {
+        quote!(#name: #pat)
+    }
+}
+
+/// collects every name a param's pattern binds
+fn bound_names(pat: &Pat, names: &mut Vec<String>) {
+    match pat {
+        Pat::Ident(pat) => names.push(pat.ident.to_string()),
+        Pat::Tuple(pat) => pat.elems.iter().for_each(|pat| bound_names(pat, names)),
+        Pat::TupleStruct(pat) => pat.elems.iter().for_each(|pat| bound_names(pat, names)),
+        Pat::Struct(pat) => pat
+            .fields
+            .iter()
+            .for_each(|field| bound_names(&field.pat, names)),
+        Pat::Type(pat) => bound_names(&pat.pat, names),
+        Pat::Reference(pat) => bound_names(&pat.pat, names),
+        Pat::Slice(pat) => pat.elems.iter().for_each(|pat| bound_names(pat, names)),
+        _ => (),
+    }
+}
+
+/// names the field behind a destructured param after its type, eg. `Vec2 { x, y }: Vec2` becomes `vec2`
+/// falling back to `arg<index>` for types without a plain name, or names taken by other params
+fn pattern_field_name(ty: &Type, index: usize, taken: &[String]) -> Ident {
+    let name = match ty {
+        Type::Path(tp) if tp.qself.is_none() => tp
+            .path
+            .segments
+            .last()
+            .map(|seg| seg.ident.to_string().to_snake_case()),
+        _ => None,
+    };
+    match name {
+        Some(name) if !taken.contains(&name) && syn::parse_str::<Ident>(&name).is_ok() => {
+            Ident::new(&name, ty.span())
+        }
+        _ => Ident::new(&format!("arg{index}"), ty.span()),
+    }
+}
+
+/// find the param which an entity command targets
+/// this is either the param marked `#[target]`, or the first `Entity` param
+fn target_entity(
+    inputs: &Punctuated<FnArg, Comma>,
+    entity_command: bool,
+) -> Result<Option<usize>, Error> {
+    let mut target = None;
+    let mut first = None;
+
+    for (index, input) in inputs.iter().enumerate() {
+        let FnArg::Typed(pt) = input else {
+            continue;
+        };
+        let is_entity = entity_command
+            && matches!(
+                pt.ty.as_ref(),
+                Type::Path(path) if path.path.segments.last().is_some_and(|seg| seg.ident == "Entity" || seg.ident == "EntityWorldMut")
+            );
+        if let Some(attr) = pt.attrs.iter().find(|attr| attr.path().is_ident("target")) {
+            if !is_entity {
+                return Err(Error::new(
+                    attr.span(),
+                    "`#[target]` must be placed on an `Entity` parameter of an entity command",
+                ));
+            }
+            if target.is_some() {
+                return Err(Error::new(
+                    attr.span(),
+                    "Only one parameter may be the `#[target]`",
+                ));
+            }
+            target = Some(index);
+        }
+        if is_entity {
+            first.get_or_insert(index);
+        }
+    }
+
+    Ok(target.or(first))
+}
+
+/// whether a type is the world, either imported or by its full path, eg. `::bevy::ecs::world::World`
+pub fn is_world(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            path.qself.is_none()
+                && path.path.segments.last().is_some_and(|seg| {
+                    seg.ident == "World" && matches!(seg.arguments, PathArguments::None)
+                })
+        }
+        _ => false,
+    }
+}
+
+/// whether a type is one of the params that exclusive systems may take besides the world
+fn is_exclusive_param(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(path) => path,
+        Type::Reference(tr) if tr.mutability.is_some() => match tr.elem.as_ref() {
+            Type::Path(path) => path,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let Some(seg) = path.path.segments.last() else {
+        return false;
+    };
+    match ty {
+        Type::Path(_) => seg.ident == "Local",
+        _ => seg.ident == "QueryState" || seg.ident == "SystemState",
+    }
+}
+
+/// whether a type is `Option<Entity>`
+fn is_optional_entity(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(seg) = path.path.segments.last() else {
+        return false;
+    };
+    let PathArguments::AngleBracketed(inner) = &seg.arguments else {
+        return false;
+    };
+    seg.ident == "Option"
+        && matches!(
+            inner.args.first(),
+            Some(GenericArgument::Type(Type::Path(inner))) if inner.path.is_ident("Entity")
+        )
+}
+
+/// whether an attribute on a fn param is one of ours and should not be emitted
+/// doc comments are included, since they aren't allowed on fn params
+pub fn is_helper_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("target") || attr.path().is_ident("arg") || attr.path().is_ident("doc")
+}
+
+/// collect the docs of a fn param, from doc comments or `#[arg(doc = "..")]`
+pub fn param_docs(attrs: &[Attribute]) -> Result<Option<String>, Error> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("doc") {
+            let Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }),
+                ..
+            }) = &attr.meta
+            else {
+                return Err(Error::new(attr.span(), "Expected a doc comment"));
+            };
+            lines.push(lit.value().trim().to_string());
+        } else if attr.path().is_ident("arg") {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("doc") {
+                    return Err(meta.error("Expected `#[arg(doc = \"..\")]`"));
+                }
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                lines.push(lit.value().trim().to_string());
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    })
+}
+
+/// copy a fn param without any of our helper attributes
+pub fn without_helper_attrs(pt: &PatType) -> PatType {
+    let mut pt = pt.clone();
+    pt.attrs.retain(|attr| !is_helper_attr(attr));
+    pt
+}
+
+/// whether rustc accepts an attribute on fn params, leaving it in place on our fn
+pub fn is_param_attr(attr: &Attribute) -> bool {
+    [
+        "allow", "warn", "deny", "forbid", "expect", "cfg", "cfg_attr",
+    ]
+    .iter()
+    .any(|name| attr.path().is_ident(name))
+}
+
+/// attributes for the field behind a param, everything but our helpers and `cfg`s
+/// fields are used by our generated items regardless, so they can't be conditional
+fn field_attrs(attrs: &[Attribute]) -> Vec<TokenStream> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            !is_helper_attr(attr)
+                && !attr.path().is_ident("cfg")
+                && !attr.path().is_ident("cfg_attr")
+        })
+        .map(forward_attr)
+        .collect()
+}
+
+/// the reference wrapped by an `Item<&C>` or `Item<&mut C>` param, erroring on anything else in `Item`
+fn item_reference(ty: &Type) -> Option<Result<&TypeReference, Error>> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let seg = path.path.segments.last()?;
+    if seg.ident != "Item" {
+        return None;
+    }
+    Some(match &seg.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(Type::Reference(tr))) if args.args.len() == 1 => Ok(tr),
+            _ => Err(Error::new(
+                ty.span(),
+                "Expected `Item<&C>` or `Item<&mut C>`",
+            )),
+        },
+        _ => Err(Error::new(
+            ty.span(),
+            "Expected `Item<&C>` or `Item<&mut C>`",
+        )),
+    })
+}
+
+/// strip our helper attributes, and those only meant for our fields, from the fn params so the fn itself can be emitted
+pub fn strip_helper_attrs(inputs: &Punctuated<FnArg, Comma>) -> Punctuated<FnArg, Comma> {
+    inputs
+        .iter()
+        .map(|input| match input {
+            FnArg::Typed(pt) => {
+                let mut pt = without_helper_attrs(pt);
+                pt.attrs.retain(is_param_attr);
+                // `Item` only exists for our macro, the fn takes the reference itself
+                if let Some(Ok(tr)) = item_reference(&pt.ty) {
+                    pt.ty = Box::new(Type::Reference(tr.clone()));
+                }
+                FnArg::Typed(pt)
+            }
+            receiver => receiver.clone(),
+        })
+        .collect()
+}
+
 /// parse the return type of a function & check whether it's our special marker or not
 pub fn return_type(output: &ReturnType) -> Result<bool, Error> {
     let ret = match &output {
@@ -299,6 +1194,26 @@ pub fn return_type(output: &ReturnType) -> Result<bool, Error> {
     Ok(ret)
 }
 
+/// the type taken by an `In` param, or `()` without one
+pub fn input_type(inputs: &Punctuated<FnArg, Comma>) -> TokenStream {
+    for arg in inputs {
+        let FnArg::Typed(pt) = arg else {
+            continue;
+        };
+        let Type::Path(tp) = pt.ty.as_ref() else {
+            continue;
+        };
+        let Some(seg) = tp.path.segments.last() else {
+            continue;
+        };
+        if let (true, PathArguments::AngleBracketed(inner)) = (seg.ident == "In", &seg.arguments) {
+            let args = &inner.args;
+            return quote!(#args);
+        }
+    }
+    quote!(())
+}
+
 /// separate out doc comments for our trait method
 pub fn docs(attrs: &[Attribute]) -> TokenStream {
     let mut docs = Vec::new();
@@ -312,9 +1227,111 @@ pub fn docs(attrs: &[Attribute]) -> TokenStream {
     quote!(#(#docs)*)
 }
 
+/// the first paragraph of a fn's doc comments, joined onto one line
+pub fn doc_summary(attrs: &[Attribute]) -> String {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if let Meta::NameValue(MetaNameValue {
+            path,
+            value: Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }),
+            ..
+        }) = &attr.meta
+        {
+            if !path.is_ident("doc") {
+                continue;
+            }
+            let value = lit.value();
+            // an empty `///` line has no lines at all, but still ends the paragraph
+            for line in value.lines().chain(value.trim().is_empty().then_some("")) {
+                let line = line.trim();
+                if line.is_empty() {
+                    if !lines.is_empty() {
+                        return lines.join(" ");
+                    }
+                } else {
+                    lines.push(line.to_string());
+                }
+            }
+        }
+    }
+    lines.join(" ")
+}
+
+/// whether an attribute silences lints, eg. `#[allow(..)]` or `#[expect(..)]`
+fn is_lint_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("allow") || attr.path().is_ident("expect")
+}
+
+/// forwards an attribute onto one of our generated items
+/// `#[expect(..)]` becomes `#[allow(..)]`, as the lint may never fire for them
+fn forward_attr(attr: &Attribute) -> TokenStream {
+    match &attr.meta {
+        Meta::List(list) if list.path.is_ident("expect") => {
+            let tokens = &list.tokens;
+            quote!(#[allow(#tokens)])
+        }
+        _ => quote!(#attr),
+    }
+}
+
+/// attributes for the structs we generate
+pub fn item_attrs(attrs: &[Attribute]) -> Vec<TokenStream> {
+    attrs.iter().map(forward_attr).collect()
+}
+
+/// just the lint attributes, for our other generated items
+pub fn lint_attrs(attrs: &[Attribute]) -> Vec<TokenStream> {
+    attrs
+        .iter()
+        .filter(|attr| is_lint_attr(attr))
+        .map(forward_attr)
+        .collect()
+}
+
+/// find type params that none of our struct fields refer to
+pub fn unused_type_params(generics: &Generics, fields: &[TokenStream]) -> Vec<Ident> {
+    fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(inner) => &inner == ident,
+            TokenTree::Group(group) => mentions(group.stream(), ident),
+            _ => false,
+        })
+    }
+
+    generics
+        .type_params()
+        .map(|param| &param.ident)
+        .filter(|ident| !fields.iter().any(|field| mentions(field.clone(), ident)))
+        .cloned()
+        .collect()
+}
+
+/// defaults are only allowed on our struct, so everything else takes the generics without them
+pub fn strip_defaults(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        match param {
+            GenericParam::Type(inner) => {
+                inner.eq_token = None;
+                inner.default = None;
+            }
+            GenericParam::Const(inner) => {
+                inner.eq_token = None;
+                inner.default = None;
+            }
+            GenericParam::Lifetime(_) => (),
+        }
+    }
+    generics
+}
+
 pub trait ExprExt {
     fn try_to_path(&self) -> Result<Path, Error>;
     fn try_to_ident(&self) -> Result<Ident, Error>;
+    fn try_to_type(&self) -> Result<Type, Error>;
+    fn try_to_vis(&self) -> Result<Visibility, Error>;
 }
 
 impl ExprExt for Expr {
@@ -357,4 +1374,38 @@ impl ExprExt for Expr {
         };
         Ok(ident)
     }
+
+    fn try_to_type(&self) -> Result<Type, Error> {
+        let ty = match &self {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => lit.parse()?,
+            Expr::Path(path) => Type::Path(syn::TypePath {
+                qself: path.qself.clone(),
+                path: path.path.clone(),
+            }),
+            value => {
+                return Err(Error::new(
+                    value.span(),
+                    format!("invalid type: `{}`", value.to_token_stream()),
+                ))
+            }
+        };
+        Ok(ty)
+    }
+
+    fn try_to_vis(&self) -> Result<Visibility, Error> {
+        let vis = match &self {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => lit.parse()?,
+            value => {
+                return Err(Error::new(
+                    value.span(),
+                    format!("invalid visibility: `{}`", value.to_token_stream()),
+                ))
+            }
+        };
+        Ok(vis)
+    }
 }
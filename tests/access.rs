@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn foo(res: Res<TestUsize>) {
+    let _ = **res;
+}
+
+#[command]
+fn bar(mut res: ResMut<TestUsize>) {
+    **res += 1;
+}
+
+#[entity_command]
+fn baz(In(entity): In<Entity>, query: Query<&Transform>) {
+    let _ = query.get(entity);
+}
+
+#[command]
+fn qux(world: &mut World) {
+    world.clear_entities();
+}
+
+/// Commands expose their access, so conflicts between them can be found
+#[test]
+fn access() {
+    let mut world = World::new();
+    let foo = FooCommand::access(&mut world);
+    let bar = BarCommand::access(&mut world);
+    let baz = BazEntityCommand::access(&mut world);
+
+    let id = world.components().resource_id::<TestUsize>().unwrap();
+    assert!(foo.has_read(id) && !foo.has_write(id));
+    assert!(bar.has_write(id));
+    assert!(!foo.is_compatible(&bar));
+    assert!(foo.is_compatible(&baz));
+}
+
+/// Exclusive commands conflict with everything
+#[test]
+fn access_exclusive() {
+    let mut world = World::new();
+    let foo = FooCommand::access(&mut world);
+    let qux = QuxCommand::access(&mut world);
+
+    assert!(qux.has_write_all());
+    assert!(!qux.is_compatible(&foo));
+}
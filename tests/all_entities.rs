@@ -0,0 +1,47 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command(all)]
+/// Subtracts `n` from the entity's `TestUsize`
+fn hurt(world: &mut World, entity: Entity, n: usize) -> &mut Self {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+#[entity_command(all, commands)]
+fn heal(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() += n;
+}
+
+/// The `all` attribute generates a method applying the entity command to many entities
+#[test]
+fn all_entities() {
+    let mut world = World::new();
+    let entities = [
+        world.spawn(TestUsize(30)).id(),
+        world.spawn(TestUsize(30)).id(),
+        world.spawn(TestUsize(30)).id(),
+    ];
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.hurt_all(entities, 10).hurt_all(entities, 5);
+    commands.heal_all(entities.to_vec(), 5);
+    commands.heal(entities[0], 5);
+
+    queue.apply(&mut world);
+
+    world
+        .hurt_all(entities, 20)
+        .heal_all(entities.iter().copied(), 5);
+    world.heal(entities[1], 5);
+
+    let mut query = world.query::<&TestUsize>();
+    assert_eq!(**query.get(&world, entities[0]).unwrap(), 10);
+    assert_eq!(**query.get(&world, entities[1]).unwrap(), 10);
+    assert_eq!(**query.get(&world, entities[2]).unwrap(), 5);
+}
@@ -0,0 +1,78 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+use std::ops::{Deref, DerefMut};
+
+mod common;
+use common::TestUsize;
+
+/// A wrapper around `Commands`, such as a custom scoping type
+struct GameCommands<'w, 's>(Commands<'w, 's>);
+
+impl<'w, 's> Deref for GameCommands<'w, 's> {
+    type Target = Commands<'w, 's>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for GameCommands<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A wrapper around `World`, such as a test harness
+struct Harness(World);
+
+impl Deref for Harness {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Harness {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[command(also_impl = "GameCommands<'_, '_>", also_impl = Harness)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(also_impl = "GameCommands<'_, '_>", also_impl = Harness)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+#[command(read_only, also_impl = Harness)]
+fn total(world: &World) -> usize {
+    **world.resource::<TestUsize>()
+}
+
+/// `also_impl` forwards the generated trait to types which deref to `Commands` or `World`
+#[test]
+fn also_impl() {
+    let mut harness = Harness(World::new());
+    harness.insert_resource(TestUsize(40));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = GameCommands(Commands::new(&mut queue, &harness));
+
+    commands.foo(5).foo(5);
+    commands.bar(10);
+
+    queue.apply(&mut harness);
+
+    assert_eq!(harness.total(), 20);
+
+    harness.foo(5).foo(5);
+    harness.bar(10);
+
+    assert_eq!(harness.total(), 0);
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(app)]
+fn foo(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(app, closure)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+/// `app` commands apply once during `Startup`, however many times the app updates
+#[test]
+fn app_startup() {
+    let mut app = App::new();
+    app.insert_resource(TestUsize(30));
+
+    app.queue_foo(10).queue_bar(5).queue_foo(10);
+
+    app.update();
+    app.update();
+
+    assert_eq!(**app.world.resource::<TestUsize>(), 5);
+}
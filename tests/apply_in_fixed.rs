@@ -0,0 +1,37 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(apply_in_fixed)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+/// Commands applied in fixed time are buffered until the next `FixedFirst` runs
+#[test]
+fn apply_in_fixed() {
+    let mut app = App::new();
+    app.insert_resource(TestUsize(50))
+        .add_plugins(FooFixedPlugin);
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &app.world);
+
+    commands.foo(5).foo(5);
+
+    queue.apply(&mut app.world);
+    app.world.foo(5);
+
+    // nothing applies outside of the fixed timestep
+    assert_eq!(**app.world.resource::<TestUsize>(), 50);
+
+    app.world.run_schedule(FixedFirst);
+    assert_eq!(**app.world.resource::<TestUsize>(), 35);
+
+    app.world.foo(10);
+    app.world.run_schedule(FixedFirst);
+    assert_eq!(**app.world.resource::<TestUsize>(), 25);
+}
@@ -0,0 +1,38 @@
+// `fuzzing` isn't a feature of this crate, leaving `BarEntityCommand` without the derive
+#![allow(unexpected_cfgs)]
+
+use arbitrary::{Arbitrary, Unstructured};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(arbitrary)]
+fn foo(world: &mut World, n: u8, name: String) {
+    **world.resource_mut::<TestUsize>() += n as usize + name.len();
+}
+
+#[entity_command(arbitrary = "fuzzing")]
+fn bar(In((entity, n)): In<(Entity, u8)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() += n as usize;
+}
+
+/// `arbitrary` commands can be generated from raw bytes
+#[test]
+fn arbitrary() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(0));
+
+    let bytes = [7u8; 64];
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..4 {
+        let command = FooCommand::arbitrary(&mut u).unwrap();
+        world.foo(command.n, command.name);
+    }
+
+    assert!(**world.resource::<TestUsize>() >= 28);
+
+    let entity = world.spawn(TestUsize(0)).id();
+    world.entity_mut(entity).bar(1);
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn foo(In(n): In<usize>, mut m: ResMut<TestUsize>, mut count: Local<usize>) {
+    *count += 1;
+    **m -= n * *count;
+}
+
+#[command]
+fn bar(world: &mut World, mut count: Local<usize>, n: usize) {
+    *count += 1;
+    **world.resource_mut::<TestUsize>() -= n * *count;
+}
+
+#[entity_command]
+fn baz(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+#[entity_command]
+fn qux(entity: Entity, world: &mut World, query: &mut QueryState<&mut TestUsize>, n: usize) {
+    **query.get_mut(world, entity).unwrap() -= n;
+}
+
+/// Batches initialize the system once, so `Local`s carry over between commands
+#[test]
+fn batch() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(12));
+
+    FooCommand::apply_batch((0..3).map(|_| FooCommand { n: 2 }), &mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+
+    world.insert_resource(TestUsize(12));
+
+    BarCommand::apply_batch(
+        vec![
+            BarCommand { n: 2 },
+            BarCommand { n: 2 },
+            BarCommand { n: 2 },
+        ],
+        &mut world,
+    );
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Batched entity commands take the target entity alongside each command
+#[test]
+fn batch_entity() {
+    let mut world = World::new();
+    let a = world.spawn(TestUsize(10)).id();
+    let b = world.spawn(TestUsize(20)).id();
+
+    BazEntityCommand::apply_batch(
+        [
+            (a, BazEntityCommand { n: 10 }),
+            (b, BazEntityCommand { n: 10 }),
+        ],
+        &mut world,
+    );
+    QuxEntityCommand::apply_batch([(b, QuxEntityCommand { n: 10 })], &mut world);
+
+    let mut query = world.query::<&TestUsize>();
+    assert!(query.iter(&world).all(|m| **m == 0));
+}
@@ -0,0 +1,118 @@
+//! Only built with `--no-default-features --features bevy_0_14`, as the codegen targets a single bevy version
+#![cfg(feature = "bevy_0_14")]
+
+// stand in for the `bevy` crate, so the macro's default paths resolve to the 0.14 crates
+extern crate self as bevy;
+pub use bevy_app_0_14 as app;
+pub use bevy_ecs_0_14 as ecs;
+pub use bevy_state_0_14 as state;
+pub use bevy_utils_0_14 as utils;
+
+use bevy_app_0_14::{App, SubApp};
+use bevy_commandify::*;
+use bevy_ecs_0_14::component::StorageType;
+use bevy_ecs_0_14::prelude::*;
+use bevy_ecs_0_14::world::CommandQueue;
+use bevy_state_0_14::app::{AppExtStates, StatesPlugin};
+use bevy_state_0_14::state::{FreelyMutableState, NextState, States};
+
+// bevy's derives would resolve to the 0.13 crates we otherwise test against
+struct Count(usize);
+
+impl Resource for Count {}
+
+impl Component for Count {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum Level {
+    #[default]
+    Menu,
+    One,
+}
+
+impl States for Level {}
+
+impl FreelyMutableState for Level {}
+
+#[command(sub_app)]
+fn grow(world: &mut World, n: usize) -> &mut Self {
+    world.resource_mut::<Count>().0 += n;
+}
+
+#[entity_command]
+fn shrink(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut Count>) {
+    query.get_mut(entity).unwrap().0 -= n;
+}
+
+#[command(run_on_enter, run_on_exit)]
+fn double(world: &mut World) {
+    world.resource_mut::<Count>().0 *= 2;
+}
+
+#[command(budget_ms = 1000, scoped_to_state = Level::One)]
+fn later(world: &mut World, n: usize) {
+    world.resource_mut::<Count>().0 += n;
+}
+
+/// Commands and entity commands are generated against bevy 0.14's paths
+#[test]
+fn bevy_0_14() {
+    let mut world = World::new();
+    world.insert_resource(Count(0));
+    let entity = world.spawn(Count(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.grow(5).grow(5);
+    commands.entity(entity).shrink(3);
+
+    queue.apply(&mut world);
+
+    world.grow(1);
+    world.entity_mut(entity).shrink(3);
+
+    assert_eq!(world.resource::<Count>().0, 11);
+    assert_eq!(world.get::<Count>(entity).unwrap().0, 4);
+}
+
+/// `sub_app` reaches the world of a 0.14 `SubApp`
+#[test]
+fn bevy_0_14_sub_app() {
+    let mut sub_app = SubApp::new();
+    sub_app.insert_resource(Count(0));
+
+    sub_app.grow(2).grow(3);
+
+    assert_eq!(sub_app.world().resource::<Count>().0, 5);
+}
+
+/// State driven commands use the states from `bevy_state`
+#[test]
+fn bevy_0_14_states() {
+    let mut app = App::new();
+    app.add_plugins(StatesPlugin)
+        .init_state::<Level>()
+        .insert_resource(Count(1))
+        .add_plugins(LaterStateScopePlugin)
+        .queue_double_on_enter(Level::One)
+        .queue_double_on_exit(Level::One);
+
+    app.world_mut().later(100);
+    app.world_mut()
+        .resource_mut::<NextState<Level>>()
+        .set(Level::One);
+    app.update();
+    assert_eq!(app.world().resource::<Count>().0, 2);
+
+    app.world_mut()
+        .resource_mut::<NextState<Level>>()
+        .set(Level::Menu);
+    app.update();
+    later_command_drain(app.world_mut());
+
+    // leaving the state dropped what was buffered
+    assert_eq!(app.world().resource::<Count>().0, 4);
+}
@@ -0,0 +1,242 @@
+//! Only built with `--no-default-features --features bevy_0_16`, as the codegen targets a single bevy version
+#![cfg(feature = "bevy_0_16")]
+
+// stand in for the `bevy` crate, so the macro's default paths resolve to the 0.16 crates
+extern crate self as bevy;
+pub use bevy_app_0_16 as app;
+pub use bevy_diagnostic_0_16 as diagnostic;
+pub use bevy_ecs_0_16 as ecs;
+pub use bevy_input_0_16 as input;
+pub use bevy_platform_0_16 as platform;
+pub use bevy_state_0_16 as state;
+
+use bevy_app_0_16::{App, SubApp};
+use bevy_commandify::*;
+use bevy_diagnostic_0_16::FrameCount;
+use bevy_ecs_0_16::component::{Mutable, StorageType};
+use bevy_ecs_0_16::prelude::*;
+use bevy_ecs_0_16::world::CommandQueue;
+use bevy_input_0_16::keyboard::KeyCode;
+use bevy_state_0_16::app::{AppExtStates, StatesPlugin};
+use bevy_state_0_16::state::{FreelyMutableState, NextState, States};
+
+// bevy's derives would resolve to the 0.13 crates we otherwise test against
+#[derive(Debug)]
+struct Count(usize);
+
+impl Resource for Count {}
+
+impl Component for Count {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum Level {
+    #[default]
+    Menu,
+    One,
+}
+
+impl States for Level {}
+
+impl FreelyMutableState for Level {}
+
+#[command(sub_app, app)]
+fn grow(world: &mut World, n: usize) -> &mut Self {
+    world.resource_mut::<Count>().0 += n;
+}
+
+#[command]
+fn bump(mut count: ResMut<Count>) {
+    count.0 += 1;
+}
+
+#[command(cached)]
+fn add(In(n): In<usize>, mut count: ResMut<Count>) {
+    count.0 += n;
+}
+
+#[command(closure)]
+fn add_later(In(n): In<usize>, mut count: ResMut<Count>) {
+    count.0 += n;
+}
+
+#[command(read_only)]
+fn total(world: &World) -> usize {
+    world.resource::<Count>().0
+}
+
+#[entity_command(commands, all, children, descendants, into_system)]
+fn shrink(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut Count>) {
+    query.get_mut(entity).unwrap().0 -= n;
+}
+
+#[entity_command(both)]
+fn halve(entity: Entity, item: Item<&mut Count>) {
+    let _ = entity;
+    item.0 /= 2;
+}
+
+#[entity_command(closure)]
+fn shrink_now(world: &mut World, entity: Entity, n: usize) {
+    world.get_mut::<Count>(entity).unwrap().0 -= n;
+}
+
+#[entity_command(spawns, test)]
+fn spawn_count(world: &mut World, entity: Entity, n: usize) {
+    world.entity_mut(entity).insert(Count(n));
+}
+
+#[entity_command]
+fn tally(_world: &mut World, entity: Entity, mut calls: Local<usize>, mut commands: Commands) {
+    *calls += 1;
+    let n = *calls;
+    commands.entity(entity).insert(Count(n));
+}
+
+#[command]
+fn reset(world: &mut World, target: Option<Entity>) {
+    match target {
+        Some(entity) => world.get_mut::<Count>(entity).unwrap().0 = 0,
+        None => world.resource_mut::<Count>().0 = 0,
+    }
+}
+
+#[command(max_per_frame = 1)]
+fn once(world: &mut World) {
+    world.resource_mut::<Count>().0 += 1;
+}
+
+#[command(hotkey = KeyCode::Space)]
+fn jump(world: &mut World) {
+    world.resource_mut::<Count>().0 += 1;
+}
+
+#[command(run_on_enter, run_on_exit)]
+fn double(world: &mut World) {
+    world.resource_mut::<Count>().0 *= 2;
+}
+
+#[command(budget_ms = 1000, scoped_to_state = Level::One)]
+fn later(world: &mut World, n: usize) {
+    world.resource_mut::<Count>().0 += n;
+}
+
+#[entity_command(apply_in_fixed, cancel_on_despawn)]
+fn fixed(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut Count>) {
+    query.get_mut(entity).unwrap().0 += n;
+}
+
+#[command_sequence]
+fn grow_then_bump(n: usize) {
+    grow(n);
+    bump();
+}
+
+/// Commands are generated against bevy 0.16, whose entity commands take an `EntityWorldMut`
+#[test]
+fn bevy_0_16() {
+    let mut world = World::new();
+    world.insert_resource(Count(0));
+    let entity = world.spawn(Count(40)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.grow(5).grow(5);
+    commands.bump();
+    commands.add(2);
+    commands.add_later(3);
+    commands.grow_then_bump(1);
+    commands.entity(entity).shrink(3);
+    commands.shrink(entity, 3);
+    commands.shrink_all([entity], 4);
+    commands.entity(entity).shrink_now(1);
+    commands.queue(ShrinkEntityCommand::new(1).with_entity(entity));
+
+    queue.apply(&mut world);
+
+    assert_eq!(world.total(), 18);
+    assert_eq!(world.get::<Count>(entity).unwrap().0, 28);
+
+    world.add(2);
+    world.add(2);
+    world.entity_mut(entity).halve();
+    world.entity_mut(entity).reset();
+    world.reset(None);
+
+    assert_eq!(world.total(), 0);
+    assert_eq!(world.get::<Count>(entity).unwrap().0, 0);
+
+    let mut system = ShrinkEntityCommand { n: 1 }.into_system();
+    let _ = &mut system;
+}
+
+/// Entity commands reach the hierarchy now in `bevy_ecs`, spawn their target and keep exclusive params working
+#[test]
+fn bevy_0_16_entities() {
+    let mut world = World::new();
+    let child = world.spawn(Count(10)).id();
+    let grandchild = world.spawn(Count(10)).id();
+    world.entity_mut(child).add_child(grandchild);
+    let parent = world.spawn_empty().add_child(child).id();
+
+    world.entity_mut(parent).shrink_children(1);
+    world.entity_mut(parent).shrink_descendants(2);
+
+    assert_eq!(world.get::<Count>(child).unwrap().0, 7);
+    assert_eq!(world.get::<Count>(grandchild).unwrap().0, 8);
+
+    let spawned = world.spawn_count(4).id();
+    assert_eq!(world.get::<Count>(spawned).unwrap().0, 4);
+
+    world.entity_mut(spawned).tally();
+    assert_eq!(world.get::<Count>(spawned).unwrap().0, 1);
+
+    // buffered until the fixed timestep, and dropped once the target is gone
+    world.entity_mut(child).fixed(1);
+    world.entity_mut(spawned).fixed(1);
+    world.despawn(spawned);
+    fixed_entity_command_drain(&mut world);
+    assert_eq!(world.get::<Count>(child).unwrap().0, 8);
+}
+
+/// App and state driven commands use the 0.16 `App`, `SubApp` and `bevy_state`, counting frames from `bevy_diagnostic`
+#[test]
+fn bevy_0_16_app() {
+    let mut sub_app = SubApp::new();
+    sub_app.insert_resource(Count(0));
+    sub_app.grow(2).grow(3);
+    assert_eq!(sub_app.world().resource::<Count>().0, 5);
+
+    let mut app = App::new();
+    app.add_plugins((StatesPlugin, LaterStateScopePlugin))
+        .init_state::<Level>()
+        .init_resource::<FrameCount>()
+        .insert_resource(Count(1))
+        .queue_grow(1)
+        .queue_double_on_enter(Level::One)
+        .queue_double_on_exit(Level::One);
+    let _ = JumpHotkeyPlugin;
+
+    app.update();
+    // startup queued the first grow, leaving a single `once` this frame
+    app.world_mut().later(100);
+    app.world_mut().once();
+    app.world_mut().once();
+    app.world_mut()
+        .resource_mut::<NextState<Level>>()
+        .set(Level::One);
+    app.update();
+    assert_eq!(app.world().resource::<Count>().0, 6);
+
+    app.world_mut()
+        .resource_mut::<NextState<Level>>()
+        .set(Level::Menu);
+    app.update();
+    later_command_drain(app.world_mut());
+
+    // leaving the state dropped what was buffered
+    assert_eq!(app.world().resource::<Count>().0, 12);
+}
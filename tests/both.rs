@@ -0,0 +1,50 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command(both)]
+fn drain(world: &mut World, entity: Entity, n: usize) -> &mut Self {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+#[entity_command(both)]
+fn drain_system(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// `both` generates a plain command alongside the entity command, taking the entity as a field
+#[test]
+fn both() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(60)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).drain(5);
+    commands.drain(entity, 5).add(DrainCommand { entity, n: 5 });
+    commands.entity(entity).add(DrainEntityCommand { n: 5 });
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).drain(5);
+    world.drain(entity, 5);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 30);
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).drain_system(10);
+    commands.drain_system(entity, 5);
+    commands.add(DrainSystemCommand { entity, n: 5 });
+
+    queue.apply(&mut world);
+
+    world.drain_system(entity, 10);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,121 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(budget_ms = 0)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(budget_ms = 1000)]
+fn baz(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(budget_ms = 1000)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+#[entity_command(commands, all, children, budget_ms = 1000)]
+fn hurt(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+#[entity_command(spawns, budget_ms = 1000)]
+fn spawn_hp(world: &mut World, entity: Entity, n: usize) {
+    world.entity_mut(entity).insert(TestUsize(n));
+}
+
+#[command(budget_ms = 1000)]
+fn heal(world: &mut World, target: Option<Entity>, n: usize) {
+    **world.get_mut::<TestUsize>(target.unwrap()).unwrap() += n;
+}
+
+/// Budgeted commands are buffered, and carried over to later runs once the budget is spent
+#[test]
+fn budget() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(50));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).foo(5).baz(5);
+
+    queue.apply(&mut world);
+    world.foo(5).baz(5);
+
+    // nothing applies until drained
+    assert_eq!(**world.resource::<TestUsize>(), 50);
+
+    // an empty budget is spent before the first application, so everything carries over
+    foo_command_drain(&mut world);
+    foo_command_drain(&mut world);
+    assert_eq!(**world.resource::<TestUsize>(), 50);
+
+    // while cheap commands all fit in a generous one
+    baz_command_drain(&mut world);
+    assert_eq!(**world.resource::<TestUsize>(), 40);
+}
+
+/// Budgeted entity commands remember their target entity
+#[test]
+fn budget_entity() {
+    let mut world = World::new();
+    let a = world.spawn(TestUsize(10)).id();
+    let b = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(a).bar(10);
+    commands.entity(b).bar(5);
+
+    queue.apply(&mut world);
+    world.entity_mut(b).bar(5);
+
+    bar_entity_command_drain(&mut world);
+
+    assert_eq!(**world.get::<TestUsize>(a).unwrap(), 0);
+    assert_eq!(**world.get::<TestUsize>(b).unwrap(), 0);
+}
+
+/// Every generated method buffers budgeted commands, not just the main one
+#[test]
+fn budget_methods() {
+    let mut world = World::new();
+    let a = world.spawn(TestUsize(100)).id();
+    let child = world.spawn(TestUsize(100)).id();
+    world.entity_mut(a).add_child(child);
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.hurt(a, 10);
+    commands.hurt_all([a], 20);
+    commands.entity(a).hurt_children(30);
+    commands.entity(a).heal(1);
+    let spawned = commands.spawn_hp(5).id();
+
+    queue.apply(&mut world);
+    world.hurt(a, 10);
+    world.hurt_all([a], 20);
+    world.entity_mut(a).hurt_children(30);
+    world.entity_mut(a).heal(1);
+
+    assert_eq!(**world.get::<TestUsize>(a).unwrap(), 100);
+    assert_eq!(**world.get::<TestUsize>(child).unwrap(), 100);
+    assert!(world.get::<TestUsize>(spawned).is_none());
+
+    hurt_entity_command_drain(&mut world);
+    spawn_hp_entity_command_drain(&mut world);
+    heal_command_drain(&mut world);
+
+    assert_eq!(**world.get::<TestUsize>(a).unwrap(), 42);
+    assert_eq!(**world.get::<TestUsize>(child).unwrap(), 40);
+    assert_eq!(**world.get::<TestUsize>(spawned).unwrap(), 5);
+}
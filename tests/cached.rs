@@ -0,0 +1,92 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(cached)]
+fn foo(mut m: ResMut<TestUsize>, mut count: Local<usize>) -> &mut Self {
+    *count += 1;
+    **m -= *count;
+}
+
+#[entity_command(cached)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// Cached commands keep their system between applications, so `Local`s persist
+#[test]
+fn cached() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(10));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo().foo();
+
+    queue.apply(&mut world);
+
+    world.foo().foo();
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Cached entity commands pass the target entity into the kept system
+#[test]
+fn cached_entity() {
+    let mut world = World::new();
+    let a = world.spawn(TestUsize(10)).id();
+    let b = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(a).bar(5);
+    commands.entity(b).bar(10);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(a).bar(5);
+
+    let mut query = world.query::<&TestUsize>();
+    assert!(query.iter(&world).all(|m| **m == 0));
+}
+
+#[derive(Component)]
+struct Enemy;
+
+#[derive(Component)]
+struct Ally;
+
+#[command(cached)]
+fn despawn_all<T: Component>(
+    mut commands: Commands,
+    query: Query<Entity, With<T>>,
+    mut runs: Local<usize>,
+    mut m: ResMut<TestUsize>,
+) {
+    *runs += 1;
+    **m -= *runs;
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Each instantiation of a generic command keeps a system of its own
+#[test]
+fn cached_generic() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(6));
+    world.spawn(Enemy);
+    world.spawn(Ally);
+
+    world.despawn_all::<Enemy>();
+    world.despawn_all::<Enemy>();
+    world.despawn_all::<Ally>();
+
+    assert_eq!(**world.resource::<TestUsize>(), 2);
+    assert_eq!(world.entities().len(), 0);
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command(budget_ms = 1000, cancel_on_despawn)]
+fn foo(entity: Entity, world: &mut World, n: usize) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+#[entity_command(budget_ms = 1000, cancel_on_despawn)]
+fn bar(entity: Entity, world: &mut World, other: Entity) {
+    world.despawn(other);
+    world.despawn(entity);
+}
+
+/// Buffered entity commands are dropped if their target despawns before they apply
+#[test]
+fn cancel_on_despawn() {
+    let mut world = World::new();
+    let a = world.spawn(TestUsize(10)).id();
+    let b = world.spawn(TestUsize(10)).id();
+
+    world.entity_mut(a).foo(5);
+    world.entity_mut(b).foo(5);
+    world.despawn(b);
+
+    foo_entity_command_drain(&mut world);
+
+    assert_eq!(**world.get::<TestUsize>(a).unwrap(), 5);
+}
+
+/// Targets despawned during the drain itself are skipped too
+#[test]
+fn cancel_on_despawn_during_drain() {
+    let mut world = World::new();
+    let a = world.spawn_empty().id();
+    let b = world.spawn_empty().id();
+    let c = world.spawn_empty().id();
+
+    world.entity_mut(a).bar(b);
+    world.entity_mut(b).bar(c);
+
+    bar_entity_command_drain(&mut world);
+
+    assert!(world.get_entity(a).is_none());
+    assert!(world.get_entity(c).is_some());
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+/// Spawns enemies around `pos`
+///
+/// More details that aren't part of the summary
+#[command]
+fn spawn_enemy(world: &mut World, pos: Vec2, count: usize) {
+    for _ in 0..count {
+        world.spawn(Transform::from_translation(pos.extend(0.)));
+    }
+}
+
+#[entity_command]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+#[command]
+fn tag(world: &mut World, target: bevy::prelude::Entity, tags: Vec<u32>, label: &'static str) {
+    world
+        .entity_mut(target)
+        .insert((TestUsize(tags.len()), Name::new(label)));
+}
+
+/// Command structs describe their docs and fields for tooling
+#[test]
+fn catalog() {
+    assert_eq!(SpawnEnemyCommand::DOC, "Spawns enemies around `pos`");
+    assert_eq!(
+        SpawnEnemyCommand::FIELDS,
+        [("pos", "Vec2"), ("count", "usize")]
+    );
+
+    assert_eq!(BarEntityCommand::DOC, "");
+    assert_eq!(BarEntityCommand::FIELDS, [("n", "usize")]);
+
+    assert_eq!(
+        TagCommand::FIELDS,
+        [
+            ("target", "bevy::prelude::Entity"),
+            ("tags", "Vec<u32>"),
+            ("label", "&'static str")
+        ]
+    );
+}
+
+/// Command structs point back at where they were declared
+#[test]
+fn catalog_source() {
+    assert_eq!(SpawnEnemyCommand::FILE, file!());
+    assert_eq!(SpawnEnemyCommand::LINE, 11);
+}
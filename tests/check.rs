@@ -0,0 +1,67 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Debug, PartialEq)]
+struct TooMuch(usize);
+
+fn at_most_ten(n: &usize) -> Result<(), TooMuch> {
+    if *n > 10 {
+        Err(TooMuch(*n))
+    } else {
+        Ok(())
+    }
+}
+
+fn non_empty(other: &Entity, name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        Err(format!("{other:?} needs a name"))
+    } else {
+        Ok(())
+    }
+}
+
+#[command(check = at_most_ten, check_error = TooMuch)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(check = non_empty)]
+fn bar(In((entity, other, name)): In<(Entity, Entity, String)>, mut commands: Commands) {
+    commands.entity(entity).insert(Name::new(name));
+    commands.entity(other).despawn();
+}
+
+/// `check` rejects bad arguments before the command is queued
+#[test]
+fn check() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn_empty().id();
+    let other = world.spawn_empty().id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    assert_eq!(commands.try_foo(15).err(), Some(TooMuch(15)));
+    commands.try_foo(5).unwrap().foo(5);
+    assert!(commands
+        .entity(entity)
+        .try_bar(other, String::new())
+        .is_err());
+
+    queue.apply(&mut world);
+
+    assert_eq!(world.try_foo(11).err(), Some(TooMuch(11)));
+    world.try_foo(10).unwrap();
+    world
+        .entity_mut(entity)
+        .try_bar(other, "named".into())
+        .unwrap();
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert!(world.get_entity(other).is_none());
+}
@@ -0,0 +1,66 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(closure)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(closure)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+#[entity_command(closure)]
+fn baz(entity: Entity, m: Item<&mut TestUsize>, mut n: usize) -> &mut Self {
+    n *= 2;
+    assert_ne!(entity, Entity::PLACEHOLDER);
+    **m -= n;
+}
+
+#[entity_command(closure)]
+fn qux(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// Closure commands queue their body directly, without generating a struct
+#[test]
+fn closure() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(40));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).foo(5);
+    commands.bar(10);
+
+    queue.apply(&mut world);
+
+    world.foo(5).foo(5);
+    world.bar(10);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Closure entity commands queue their body directly, without generating a struct
+#[test]
+fn closure_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(40)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).baz(5).qux(5);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).baz(5).qux(5);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}
@@ -0,0 +1,93 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+mod combat {
+    use super::*;
+
+    #[command]
+    pub fn attack(world: &mut World, n: usize) {
+        **world.resource_mut::<TestUsize>() -= n;
+    }
+
+    pub mod magic {
+        use super::*;
+
+        #[command]
+        pub fn fireball(world: &mut World) {
+            **world.resource_mut::<TestUsize>() -= 10;
+        }
+    }
+}
+
+mod items {
+    use bevy::prelude::*;
+    use bevy_commandify::*;
+
+    #[entity_command]
+    pub fn heal(world: &mut World, entity: Entity, n: usize) {
+        **world.get_mut::<super::TestUsize>(entity).unwrap() += n;
+    }
+
+    command_group!(
+        /// Groups may also be declared alongside their commands
+        pub mod prelude { EntityCommandsHealExt }
+    );
+}
+
+command_group!(pub mod combat_commands {
+    combat::CommandsAttackExt,
+    self::combat::magic::CommandsFireballExt,
+    crate::items::prelude::EntityCommandsHealExt,
+});
+
+command_group!(pub mod commands_prelude {
+    combat::{magic::*, CommandsAttackExt},
+    items::*,
+});
+
+/// A single import brings every command of a group into scope
+#[test]
+fn command_group() {
+    use combat_commands::*;
+
+    let mut world = World::new();
+    world.insert_resource(TestUsize(40));
+    let entity = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.attack(5);
+    commands.fireball();
+    commands.entity(entity).heal(5);
+
+    queue.apply(&mut world);
+
+    world.attack(5);
+    world.fireball();
+    world.entity_mut(entity).heal(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 10);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}
+
+/// Groups may glob whole modules of commands, much like a prelude
+#[test]
+fn command_group_glob() {
+    use commands_prelude::*;
+
+    let mut world = World::new();
+    world.insert_resource(TestUsize(30));
+    let entity = world.spawn(TestUsize(0)).id();
+
+    world.attack(5);
+    world.fireball();
+    world.entity_mut(entity).heal(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 15);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 5);
+}
@@ -0,0 +1,72 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[commandify_trait(delegate = Combat)]
+trait CombatApi {
+    /// Deals `damage` to the shared pool
+    fn attack(world: &mut World, damage: usize);
+
+    #[entity_command(name = "heal")]
+    fn restore(entity: Entity, world: &mut World, amount: usize);
+
+    #[command(no_world)]
+    fn reset(res: ResMut<TestUsize>);
+}
+
+struct Combat;
+
+impl CombatApi for Combat {
+    fn attack(world: &mut World, damage: usize) {
+        **world.resource_mut::<TestUsize>() -= damage;
+    }
+
+    fn restore(entity: Entity, world: &mut World, amount: usize) {
+        **world.get_mut::<TestUsize>(entity).unwrap() += amount;
+    }
+
+    fn reset(mut res: ResMut<TestUsize>) {
+        **res = 0;
+    }
+}
+
+/// Each trait method becomes a command calling the delegate's impl
+#[test]
+fn commandify_trait() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(10));
+    let entity = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.attack(5);
+    commands.entity(entity).heal(5);
+
+    queue.apply(&mut world);
+
+    world.attack(5);
+    world.entity_mut(entity).heal(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}
+
+/// Methods keep any args of their own
+#[test]
+fn commandify_trait_args() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(10));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.reset();
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(AttackCommand::DOC, "Deals `damage` to the shared pool");
+}
@@ -0,0 +1,41 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(constructor)]
+fn foo(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(constructor = make_bar, closure)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+#[entity_command(constructor)]
+fn baz(entity: Entity, world: &mut World, n: usize) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+/// `constructor` generates free functions returning the command
+#[test]
+fn constructor() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.add(foo_command(5));
+    commands.add(make_bar(15));
+    commands.entity(entity).add(baz_entity_command(10));
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
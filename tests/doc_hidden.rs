@@ -0,0 +1,40 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(doc_hidden)]
+/// Docs are kept on the method
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(doc_hidden = all, both)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// Hidden items are still usable
+#[test]
+fn doc_hidden() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).add(FooCommand { n: 5 });
+    commands.entity(entity).bar(5);
+    commands.add(BarCommand { entity, n: 5 });
+
+    queue.apply(&mut world);
+
+    world.foo(10);
+    world.entity_mut(entity).bar(10);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -11,21 +11,43 @@ fn foo(_world: &mut World) {}
 #[entity_command(bevy_ecs)]
 fn bar(_world: &mut World, _entity: Entity) {}
 
+#[command(bevy_ecs)]
+fn baz(In(_n): In<usize>) {}
+
+// sequences name the world by its path, so don't need it imported
+mod sequence {
+    use super::{CommandsBazExt, CommandsFooExt};
+    use bevy_commandify::*;
+
+    #[command_sequence(bevy_ecs)]
+    pub fn foo_then_baz(n: usize) {
+        foo();
+        baz(n);
+    }
+}
+
 /// The `ecs` attribute should point this macro to the correct `bevy_ecs`-equivalent root
 #[test]
 fn ecs_name() {
     let mut world = World::new();
 
     let mut queue = CommandQueue::default();
-    let mut commands = Commands::new(&mut queue, &mut world);
+    let mut commands = Commands::new(&mut queue, &world);
 
     // Call via Commands
     commands.foo();
     commands.spawn_empty().bar();
+    commands.baz(1);
+    {
+        use sequence::CommandsFooThenBazExt;
+        commands.foo_then_baz(1);
+        world.foo_then_baz(1);
+    }
 
     // Call via World
     world.foo();
     world.spawn_empty().bar();
+    world.baz(1);
 
     queue.apply(&mut world);
 }
@@ -0,0 +1,92 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Component, Debug, Deref, DerefMut)]
+struct Step(usize);
+
+#[entity_command]
+fn foo(entity: Entity, m: Item<&mut TestUsize>, step: Item<&Step>, n: usize) -> &mut Self {
+    let _ = entity;
+    **m -= **step * n;
+}
+
+#[entity_command]
+fn bar(world: &mut World, entity: Entity, m: Item<&mut TestUsize>) {
+    **m -= 5;
+    assert!(world.get_entity(entity).is_some());
+}
+
+#[entity_command]
+fn label(world: &mut World, entity: Entity, name: &'static str) {
+    world.entity_mut(entity).insert(Name::new(name));
+}
+
+/// Entity commands should fetch `Item<&C>` and `Item<&mut C>` params from the target entity
+#[test]
+fn fetched_items() {
+    let mut world = World::new();
+    let entity = world.spawn((TestUsize(14), Step(2))).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).foo(2).foo(1);
+    commands.entity(entity).add(FooEntityCommand { n: 1 });
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).foo(2).foo(1);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
+
+/// Items may be declared alongside the world, as long as they aren't used at the same time
+#[test]
+fn fetched_items_world() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).bar();
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).bar();
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
+
+/// Targets missing an item are left alone, rather than panicking
+#[test]
+fn fetched_items_missing() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).foo(1);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).foo(1);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}
+
+/// Plain references are stored as fields, like any other param
+#[test]
+fn reference_fields() {
+    let mut world = World::new();
+    let entity = world.spawn_empty().id();
+
+    world.entity_mut(entity).label("player");
+
+    assert_eq!(world.get::<Name>(entity).unwrap().as_str(), "player");
+}
@@ -0,0 +1,52 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command]
+fn foo(mut entity: EntityWorldMut, n: usize) -> &mut Self {
+    let mut m = entity.get_mut::<TestUsize>().unwrap();
+    **m -= n;
+}
+
+#[entity_command]
+fn bar(entity: EntityWorldMut) {
+    entity.despawn();
+}
+
+/// Entity commands taking `EntityWorldMut` should be handed the target entity
+#[test]
+fn entity_world_mut() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(30)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).foo(10).foo(5);
+    commands.entity(entity).add(FooEntityCommand { n: 5 });
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).foo(10);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
+
+/// The body may consume the `EntityWorldMut`
+#[test]
+fn entity_world_mut_despawn() {
+    let mut world = World::new();
+    let entity = world.spawn_empty().id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).bar();
+
+    queue.apply(&mut world);
+
+    assert!(world.get_entity(entity).is_none());
+}
@@ -0,0 +1,44 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(example_docs)]
+/// Docs are followed by an example
+fn foo(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(example_docs)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+#[command(read_only, example_docs)]
+fn baz(world: &World) -> usize {
+    **world.resource::<TestUsize>()
+}
+
+/// Example docs leave the generated methods unchanged
+#[test]
+fn example_docs() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(10);
+    commands.entity(entity).bar(10);
+
+    queue.apply(&mut world);
+
+    world.foo(10);
+    world.entity_mut(entity).bar(10);
+
+    assert_eq!(world.baz(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,80 @@
+use bevy::ecs::system::{CommandQueue, SystemState};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn foo(world: &mut World, mut count: Local<usize>, query: &mut QueryState<&mut TestUsize>) {
+    *count += 1;
+    for mut m in query.iter_mut(world) {
+        **m -= *count * 5;
+    }
+}
+
+#[command]
+fn bar(n: usize, world: &mut World, state: &mut SystemState<ResMut<TestUsize>>) -> &mut Self {
+    **state.get_mut(world) -= n;
+}
+
+#[entity_command]
+fn baz(entity: Entity, world: &mut World, query: &mut QueryState<&mut TestUsize>, n: usize) {
+    **query.get_mut(world, entity).unwrap() -= n;
+}
+
+/// Exclusive commands may take `Local` and `QueryState` params alongside the world
+#[test]
+fn exclusive_params() {
+    let mut world = World::new();
+    world.spawn(TestUsize(10));
+    world.spawn(TestUsize(10));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo();
+
+    queue.apply(&mut world);
+
+    world.foo();
+
+    let mut query = world.query::<&TestUsize>();
+    assert!(query.iter(&world).all(|m| **m == 0));
+}
+
+/// Exclusive commands may take a `SystemState` param alongside the world
+#[test]
+fn exclusive_system_state() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.bar(5).bar(5);
+
+    queue.apply(&mut world);
+
+    world.bar(5).bar(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Exclusive entity commands may take exclusive system params alongside the world
+#[test]
+fn exclusive_params_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).baz(10);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).baz(10);
+
+    assert_eq!(**world.query::<&TestUsize>().single(&world), 0);
+}
@@ -0,0 +1,36 @@
+use arbitrary::{Arbitrary, Unstructured};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(arbitrary)]
+fn foo(world: &mut World, n: u8, #[arbitrary(default)] name: String) {
+    **world.resource_mut::<TestUsize>() += n as usize + name.len();
+}
+
+#[entity_command(arbitrary)]
+fn bar(
+    In((entity, n, name)): In<(Entity, u8, String)>,
+    #[allow(unused_mut)] mut query: Query<&mut TestUsize>,
+) {
+    **query.get_mut(entity).unwrap() += n as usize + name.len();
+}
+
+/// Unknown param attributes are moved onto the generated fields, and lints stay on the fn too
+#[test]
+fn field_attrs() {
+    let bytes = [7u8; 64];
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..4 {
+        let command = FooCommand::arbitrary(&mut u).unwrap();
+        assert_eq!(command.name, "");
+    }
+
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(0)).id();
+    world.entity_mut(entity).bar(1, "a".into());
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 2);
+}
@@ -0,0 +1,44 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+mod library {
+    use super::*;
+
+    #[command(fn_vis = "")]
+    pub fn foo(mut m: ResMut<TestUsize>) {
+        **m -= 5;
+    }
+
+    #[entity_command(fn_vis = "pub(crate)")]
+    pub fn bar(entity: Entity, world: &mut World, n: usize) {
+        **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+    }
+}
+
+use library::*;
+
+/// Generated items keep the fn's written visibility, while the fn itself follows `fn_vis`
+#[test]
+fn fn_vis() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(10));
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo();
+    commands.add(library::FooCommand);
+    commands
+        .entity(entity)
+        .add(library::BarEntityCommand { n: 10 });
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
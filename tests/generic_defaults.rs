@@ -0,0 +1,60 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+use std::ops::DerefMut;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn foo<const N: usize = 5>(mut m: ResMut<TestUsize>) -> &mut Self {
+    **m -= N;
+}
+
+#[entity_command]
+fn bar<T: Component + DerefMut<Target = usize> = TestUsize>(
+    entity: Entity,
+    world: &mut World,
+    n: usize,
+) {
+    **world.get_mut::<T>(entity).unwrap() -= n;
+}
+
+/// Generic defaults carry over to the struct, so naming its type needs no generics
+#[test]
+fn generic_defaults() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    let command: FooCommand = FooCommand;
+    commands.add(command);
+    commands.foo::<5>();
+
+    queue.apply(&mut world);
+
+    world.foo::<10>();
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Generic type defaults carry over to entity command structs
+#[test]
+fn generic_defaults_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    let command: BarEntityCommand = BarEntityCommand::new(10);
+    commands.entity(entity).add(command);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).bar::<TestUsize>(10);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,48 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command(children, descendants)]
+fn drain(world: &mut World, entity: Entity, n: usize) -> &mut Self {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+#[entity_command(descendants)]
+fn fill(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() += n;
+}
+
+/// `children` and `descendants` apply the command to the target's hierarchy, as it is once applied
+#[test]
+fn hierarchy() {
+    let mut world = World::new();
+    let root = world.spawn(TestUsize(100)).id();
+    let child = world.spawn(TestUsize(100)).set_parent(root).id();
+    let grandchild = world.spawn(TestUsize(100)).set_parent(child).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    // only parented once the queue applies, which is still in time for the commands below
+    let late = commands.spawn(TestUsize(100)).set_parent(root).id();
+
+    commands
+        .entity(root)
+        .drain_children(10)
+        .drain_descendants(5);
+    commands.entity(child).fill_descendants(1);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(child).drain_children(20).drain(1);
+    world.entity_mut(root).fill_descendants(2);
+
+    let value = |entity| **world.get::<TestUsize>(entity).unwrap();
+    assert_eq!(value(root), 100);
+    assert_eq!(value(child), 86);
+    assert_eq!(value(grandchild), 78);
+    assert_eq!(value(late), 87);
+}
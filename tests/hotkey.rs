@@ -0,0 +1,52 @@
+use bevy::input::ButtonInput;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(hotkey = KeyCode::F5)]
+fn foo(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n + 5;
+}
+
+#[command(hotkey = [KeyCode::ControlLeft, KeyCode::KeyR], closure)]
+fn bar(mut m: ResMut<TestUsize>) {
+    **m -= 10;
+}
+
+/// Hotkey plugins queue the command with default fields when the key is first pressed
+#[test]
+fn hotkey() {
+    let mut app = App::new();
+    app.insert_resource(TestUsize(40))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .add_plugins((FooHotkeyPlugin, BarHotkeyPlugin));
+
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 40);
+
+    app.world
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::F5);
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 35);
+
+    // held keys don't repeat the command
+    app.world.resource_mut::<ButtonInput<KeyCode>>().clear();
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 35);
+
+    // chords need every key held, applying once the last is pressed
+    app.world
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::KeyR);
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 35);
+
+    let mut input = app.world.resource_mut::<ButtonInput<KeyCode>>();
+    input.clear();
+    input.press(KeyCode::ControlLeft);
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 25);
+}
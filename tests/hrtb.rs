@@ -0,0 +1,80 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+fn is_positive(world: &World) -> bool {
+    **world.resource::<TestUsize>() > 0
+}
+
+#[command]
+fn foo(world: &mut World, when: for<'a> fn(&'a World) -> bool, n: usize) -> &mut Self {
+    if when(world) {
+        **world.resource_mut::<TestUsize>() -= n;
+    }
+}
+
+#[command]
+fn bar<F>(In(when): In<F>, mut m: ResMut<TestUsize>)
+where
+    F: for<'a> Fn(&'a usize) -> bool + Send + Sync + 'static,
+{
+    if when(&m) {
+        **m -= 5;
+    }
+}
+
+#[entity_command]
+fn baz<F: for<'a> Fn(&'a mut TestUsize) + Send + 'static>(entity: Entity, world: &mut World, f: F) {
+    f(&mut world.get_mut::<TestUsize>(entity).unwrap());
+}
+
+#[command]
+fn qux(In(when): In<for<'a> fn(&'a usize) -> bool>, mut m: ResMut<TestUsize>) {
+    if when(&m) {
+        **m -= 5;
+    }
+}
+
+#[command]
+fn quux<T>(f: T, world: &mut World)
+where
+    T: Send + 'static,
+    for<'a> T: Fn(&'a usize) -> usize,
+{
+    **world.resource_mut::<TestUsize>() -= f(&2);
+}
+
+/// Fields may be higher-ranked fn pointers, and generics may have higher-ranked bounds
+#[test]
+fn hrtb() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(35));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(is_positive, 5).bar(|n: &usize| *n >= 5);
+    commands.qux(|n| *n >= 5);
+
+    queue.apply(&mut world);
+
+    world.foo(is_positive, 5).bar(|n: &usize| *n >= 5);
+    world.qux(|n| *n >= 5);
+    world.quux(|n| n + 3);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Higher-ranked bounds inline with the generic param carry over to entity commands
+#[test]
+fn hrtb_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(10)).id();
+
+    world.entity_mut(entity).baz(|m: &mut TestUsize| **m -= 10);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,68 @@
+use bevy::ecs::system::{Command, CommandQueue};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+pub struct SpawnRequest {
+    pub count: usize,
+    pub cost: usize,
+}
+
+pub struct Heal {
+    pub amount: usize,
+}
+
+#[command]
+fn spawn_units(In(request): In<SpawnRequest>, mut m: ResMut<TestUsize>) {
+    **m -= request.count * request.cost;
+}
+
+#[command]
+fn spawn_destructured(
+    In(SpawnRequest { count, cost }): In<SpawnRequest>,
+    mut m: ResMut<TestUsize>,
+) {
+    **m -= count * cost;
+}
+
+#[entity_command]
+fn restore(In((entity, Heal { amount })): In<(Entity, Heal)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() += amount;
+}
+
+/// Commands may take a struct as their input, destructured or not
+#[test]
+fn input_struct() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.spawn_units(SpawnRequest { count: 2, cost: 5 });
+
+    queue.apply(&mut world);
+
+    world.spawn_destructured(SpawnRequest { count: 5, cost: 2 });
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+
+    // destructured inputs become a field named after their type
+    SpawnDestructuredCommand {
+        spawn_request: SpawnRequest { count: 0, cost: 0 },
+    }
+    .apply(&mut world);
+}
+
+/// Destructured inputs may sit alongside other inputs
+#[test]
+fn input_struct_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(0)).id();
+
+    world.entity_mut(entity).restore(Heal { amount: 10 });
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}
@@ -0,0 +1,40 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn clear_selection(world: &mut World) {
+    **world.resource_mut::<TestUsize>() = 0;
+}
+
+#[entity_command]
+fn unmark<T: Component>(entity: Entity, world: &mut World) {
+    world.entity_mut(entity).remove::<T>();
+}
+
+const RESETS: [ClearSelectionCommand; 2] = [ClearSelectionCommand::INSTANCE; 2];
+
+/// Field-less command structs have a ready-made `INSTANCE`, usable in `const` items
+#[test]
+fn instance() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(10));
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    let [reset, _] = RESETS;
+    commands.add(reset);
+    commands
+        .entity(entity)
+        .add(UnmarkEntityCommand::<TestUsize>::INSTANCE);
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert!(world.get::<TestUsize>(entity).is_none());
+}
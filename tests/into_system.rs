@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(into_system)]
+fn drain(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(into_system)]
+fn fill(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() += n;
+}
+
+/// `into_system` lets a command be scheduled or registered as a one-shot system
+#[test]
+fn into_system() {
+    let mut app = App::new();
+    app.insert_resource(TestUsize(50))
+        .add_systems(Update, DrainCommand { n: 5 }.into_system());
+
+    app.update();
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 40);
+
+    let entity = app.world.spawn(TestUsize(0)).id();
+    let fill = app
+        .world
+        .register_system(FillEntityCommand { n: 3 }.into_system());
+    app.world.run_system_with_input(fill, entity).unwrap();
+    app.world.run_system_with_input(fill, entity).unwrap();
+    assert_eq!(**app.world.get::<TestUsize>(entity).unwrap(), 6);
+}
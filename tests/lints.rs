@@ -0,0 +1,57 @@
+#![deny(unused)]
+
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+#[expect(unused_variables)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= 10;
+}
+
+#[allow(clippy::too_many_arguments)]
+#[entity_command(both)]
+fn bar(
+    world: &mut World,
+    entity: Entity,
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    e: usize,
+    f: usize,
+) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= a + b + c + d + e + f;
+}
+
+#[command]
+#[expect(unused_variables)]
+fn baz(In(n): In<usize>, mut m: ResMut<TestUsize>, count: Local<usize>) {
+    **m -= n;
+}
+
+/// Lint attributes on the fn should carry over to the generated items
+#[test]
+fn lints() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(30));
+    let entity = world.spawn(TestUsize(12)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).baz(5);
+    commands.entity(entity).bar(1, 1, 1, 1, 1, 1);
+
+    queue.apply(&mut world);
+
+    world.foo(5).baz(5);
+    world.bar(entity, 1, 1, 1, 1, 1, 1);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
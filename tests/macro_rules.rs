@@ -0,0 +1,62 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+// params and bodies written inside a declarative macro carry its hygiene, which our generated code must agree with
+macro_rules! drain_command {
+    ($name:ident, $scale:expr) => {
+        #[command]
+        fn $name(world: &mut World, n: usize) -> &mut Self {
+            **world.resource_mut::<TestUsize>() -= n * $scale;
+        }
+    };
+}
+
+macro_rules! drain_system {
+    ($name:ident) => {
+        #[command(cached)]
+        fn $name(In(n): In<usize>, mut value: ResMut<TestUsize>) {
+            **value -= n;
+        }
+    };
+}
+
+macro_rules! drain_entity {
+    ($name:ident) => {
+        #[entity_command(all, budget_ms = 10, cancel_on_despawn)]
+        fn $name(_entity: Entity, value: Item<&mut TestUsize>, n: usize) {
+            **value -= n;
+        }
+    };
+}
+
+drain_command!(drain_double, 2);
+drain_command!(drain_triple, 3);
+drain_system!(drain_once);
+drain_entity!(drain_entity);
+
+/// Commands may be declared by declarative macros, with the generated items named after `$name`
+#[test]
+fn macro_rules() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(100));
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.drain_double(5).drain_triple(5);
+    commands.add(DrainOnceCommand { n: 5 });
+    commands.entity(entity).drain_entity(5);
+
+    queue.apply(&mut world);
+
+    world.drain_once(5);
+    drain_entity_entity_command_drain(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 65);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 5);
+}
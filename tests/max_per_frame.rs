@@ -0,0 +1,75 @@
+use bevy::core::FrameCount;
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Resource, Default)]
+struct Overflowed(Vec<usize>);
+
+fn overflowed(command: FooCommand, world: &mut World) {
+    world.resource_mut::<Overflowed>().0.push(command.n);
+}
+
+#[command(max_per_frame = 2, on_overflow = overflowed)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(max_per_frame = 1)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) -> &mut Self {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// Applications beyond `max_per_frame` are handed to `on_overflow` instead, until the next frame
+#[test]
+fn max_per_frame() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    world.init_resource::<FrameCount>();
+    world.init_resource::<Overflowed>();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).foo(5).foo(1).foo(2);
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 10);
+    assert_eq!(world.resource::<Overflowed>().0, vec![1, 2]);
+
+    world.resource_mut::<FrameCount>().0 += 1;
+    world.foo(5).foo(5).foo(3);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(world.resource::<Overflowed>().0, vec![1, 2, 3]);
+}
+
+/// Entity commands are limited across all entities, and dropped without `on_overflow`
+#[test]
+fn max_per_frame_entity() {
+    let mut world = World::new();
+    world.init_resource::<FrameCount>();
+    let a = world.spawn(TestUsize(10)).id();
+    let b = world.spawn(TestUsize(10)).id();
+
+    world.entity_mut(a).bar(5).bar(5);
+    world.entity_mut(b).bar(5);
+
+    assert_eq!(**world.get::<TestUsize>(a).unwrap(), 5);
+    assert_eq!(**world.get::<TestUsize>(b).unwrap(), 10);
+}
+
+/// Without `FrameCount` there are no frames to limit applications to, so none are dropped
+#[test]
+fn max_per_frame_without_frame_count() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(10)).id();
+
+    world.entity_mut(entity).bar(2).bar(2).bar(2);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 4);
+}
@@ -0,0 +1,59 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(name = "DespawnAllEnemies", method = "clear_enemies")]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(method = drain, all)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// `method` renames the trait method, while `name` still drives the type names
+#[test]
+fn method() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(30));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands
+        .clear_enemies(5)
+        .add(DespawnAllEnemiesCommand { n: 5 });
+    CommandsDespawnAllEnemiesExt::clear_enemies(&mut commands, 5);
+
+    queue.apply(&mut world);
+
+    world.clear_enemies(15);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// `method` also renames the methods generated by `commands` and `all`
+#[test]
+fn method_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(30)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).drain(5);
+    commands.entity(entity).add(BarEntityCommand { n: 5 });
+    EntityCommandsBarExt::drain(&mut commands.entity(entity), 5);
+    commands.drain_all([entity], 5);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).drain(5);
+    CommandsBarExt::drain_all(&mut world, [entity], 5);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,69 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command(commands)]
+/// Moves `n` from the target entity to `dest`
+fn give(world: &mut World, source: Entity, dest: Entity, n: usize) -> &mut Self {
+    **world.get_mut::<TestUsize>(source).unwrap() -= n;
+    **world.get_mut::<TestUsize>(dest).unwrap() += n;
+}
+
+#[entity_command]
+fn give_system(
+    In((source, dest, n)): In<(Entity, Entity, usize)>,
+    mut query: Query<&mut TestUsize>,
+) {
+    **query.get_mut(source).unwrap() -= n;
+    **query.get_mut(dest).unwrap() += n;
+}
+
+/// The first entity is the target, the others become fields
+#[test]
+fn first_entity_is_target() {
+    let mut world = World::new();
+    let source = world.spawn(TestUsize(30)).id();
+    let dest = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(source).give(dest, 5).give_system(dest, 5);
+    commands
+        .entity(source)
+        .add(GiveEntityCommand { dest, n: 5 });
+    commands
+        .entity(source)
+        .add(GiveSystemEntityCommand { dest, n: 5 });
+
+    queue.apply(&mut world);
+
+    world.entity_mut(source).give(dest, 5).give_system(dest, 5);
+
+    assert_eq!(**world.get::<TestUsize>(source).unwrap(), 0);
+    assert_eq!(**world.get::<TestUsize>(dest).unwrap(), 30);
+}
+
+/// The `commands` attribute also generates a method taking the target explicitly
+#[test]
+fn commands_method() {
+    let mut world = World::new();
+    let source = world.spawn(TestUsize(30)).id();
+    let dest = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.give(source, dest, 10).give(source, dest, 5);
+    CommandsGiveExt::give(&mut commands, source, dest, 5);
+
+    queue.apply(&mut world);
+
+    world.give(source, dest, 5).give(source, dest, 5);
+
+    assert_eq!(**world.get::<TestUsize>(source).unwrap(), 0);
+    assert_eq!(**world.get::<TestUsize>(dest).unwrap(), 30);
+}
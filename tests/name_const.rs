@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+#[command]
+fn spawn_enemy(world: &mut World) {
+    world.spawn_empty();
+}
+
+#[entity_command(name = "heal", struct_name = "Restore")]
+fn bar<T: Component>(entity: Entity, world: &mut World) {
+    world.entity_mut(entity).remove::<T>();
+}
+
+/// Command structs carry the command's name as a constant
+#[test]
+fn name_const() {
+    const NAMES: [&str; 2] = [SpawnEnemyCommand::NAME, Restore::<Transform>::NAME];
+
+    assert_eq!(NAMES, ["spawn_enemy", "heal"]);
+}
@@ -0,0 +1,58 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn foo(world: &mut World, mut commands: Commands, n: usize) -> &mut Self {
+    let m = **world.resource::<TestUsize>();
+    commands.insert_resource(TestUsize(m - n));
+    // queued commands are applied at the end of the body
+    assert_eq!(**world.resource::<TestUsize>(), m);
+}
+
+#[entity_command]
+fn bar(world: &mut World, entity: Entity, mut commands: Commands) {
+    commands.entity(entity).insert(TestUsize(0));
+    world.entity_mut(entity).insert(TestUsize(10));
+}
+
+/// Exclusive commands may queue further commands through a `Commands` param
+#[test]
+fn nested_commands() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).foo(5);
+
+    queue.apply(&mut world);
+
+    world.foo(5).foo(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Commands queued by an exclusive entity command are applied after its body
+#[test]
+fn nested_commands_entity() {
+    let mut world = World::new();
+    let entity = world.spawn_empty().id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).bar();
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+
+    world.entity_mut(entity).bar();
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,49 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(no_original)]
+fn foo(mut m: ResMut<TestUsize>) {
+    **m -= 5;
+}
+
+#[command(no_original, cached)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+#[entity_command(no_original)]
+fn baz(entity: Entity, world: &mut World, n: usize) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+// the fn's name is free for other items to use
+#[allow(dead_code)]
+fn foo() {}
+
+/// Commands hiding their fn still generate everything else
+#[test]
+fn no_original() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(10)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo();
+    commands.bar(5);
+    commands.entity(entity).baz(5);
+
+    queue.apply(&mut world);
+
+    world.foo();
+    BarCommand::apply_batch([BarCommand { n: 5 }], &mut world);
+    world.entity_mut(entity).baz(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
@@ -0,0 +1,102 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+/// A struct shared with other code, such as netcode
+pub struct Drain {
+    n: usize,
+}
+
+pub struct DrainEntity {
+    n: usize,
+}
+
+#[command(no_struct, struct_name = Drain)]
+fn drain(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(no_struct, struct_name = DrainEntity)]
+fn drain_entity(
+    In((entity, n)): In<(Entity, usize)>,
+    mut query: Query<&mut TestUsize>,
+) -> &mut Self {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// `no_struct` commands are implemented for an existing struct with matching fields
+#[test]
+fn no_struct() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.drain(5).add(Drain { n: 5 });
+    commands
+        .entity(entity)
+        .drain_entity(5)
+        .add(DrainEntity { n: 5 });
+
+    queue.apply(&mut world);
+
+    world.drain(10);
+    world.entity_mut(entity).drain_entity(10);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
+
+pub struct Buffered {
+    n: usize,
+}
+
+pub struct BufferedEntity {
+    n: usize,
+}
+
+#[command(no_struct, struct_name = Buffered, budget_ms = 1000)]
+fn buffered(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(no_struct, struct_name = BufferedEntity, apply_in_fixed)]
+fn buffered_entity(
+    In((entity, n)): In<(Entity, usize)>,
+    mut query: Query<&mut TestUsize>,
+) -> &mut Self {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// Buffered `no_struct` commands are drained one at a time, as there's no `apply_batch`
+#[test]
+fn no_struct_buffered() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.buffered(5).buffered(5);
+    commands
+        .entity(entity)
+        .buffered_entity(5)
+        .buffered_entity(5);
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 20);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 20);
+
+    buffered_drain(&mut world);
+    buffered_entity_drain(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 10);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}
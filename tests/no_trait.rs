@@ -13,7 +13,7 @@ fn struct_command_still_works() {
     let mut world = World::new();
 
     let mut queue = CommandQueue::default();
-    let mut commands = Commands::new(&mut queue, &mut world);
+    let mut commands = Commands::new(&mut queue, &world);
 
     commands.add(FooCommand);
     commands.spawn_empty().add(BarEntityCommand);
@@ -0,0 +1,54 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+/// Adds `n` to the entity if there is one, otherwise to the resource
+fn grow(world: &mut World, target: Option<Entity>, n: usize) -> &mut Self {
+    match target {
+        Some(entity) => **world.get_mut::<TestUsize>(entity).unwrap() += n,
+        None => **world.resource_mut::<TestUsize>() += n,
+    }
+}
+
+#[command]
+fn sub(
+    In((n, target)): In<(usize, Option<Entity>)>,
+    mut res: ResMut<TestUsize>,
+    mut query: Query<&mut TestUsize>,
+) {
+    match target {
+        Some(entity) => **query.get_mut(entity).unwrap() -= n,
+        None => **res -= n,
+    }
+}
+
+/// `Option<Entity>` commands may be issued from `Commands`, or an entity's commands
+#[test]
+fn optional_entity() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(0));
+    let entity = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.grow(None, 10).grow(Some(entity), 5);
+    commands.entity(entity).grow(5).sub(5);
+    commands.add(GrowCommand {
+        target: Some(entity),
+        n: 5,
+    });
+
+    queue.apply(&mut world);
+
+    world.grow(None, 5).sub(5, None);
+    world.entity_mut(entity).grow(5).sub(5);
+    EntityCommandsGrowExt::grow(&mut world.entity_mut(entity), 5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 10);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 15);
+}
@@ -0,0 +1,46 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+/// Subtracts from the resource
+#[command]
+pub fn foo(
+    world: &mut World,
+    /// How much to subtract
+    n: usize,
+    #[arg(doc = "How many times to subtract")] times: usize,
+) {
+    **world.resource_mut::<TestUsize>() -= n * times;
+}
+
+/// Subtracts from the resource within a system
+#[command]
+pub fn bar(
+    /// How much to subtract
+    In(n): In<usize>,
+    mut m: ResMut<TestUsize>,
+) {
+    **m -= n;
+}
+
+/// Param docs are accepted and stripped from the original fn
+#[test]
+fn param_docs() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(40));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5, 2);
+    commands.add(FooCommand { n: 5, times: 2 });
+    commands.bar(10);
+    commands.add(BarCommand { n: 10 });
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
@@ -0,0 +1,94 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Component)]
+struct Marker;
+
+#[allow(clippy::type_complexity)]
+#[command]
+fn foo(
+    In(n): In<usize>,
+    mut set: ParamSet<(
+        Query<&mut TestUsize, With<Marker>>,
+        Query<&mut TestUsize, Without<Marker>>,
+    )>,
+) -> &mut Self {
+    for mut m in set.p0().iter_mut() {
+        **m -= n;
+    }
+    for mut m in set.p1().iter_mut() {
+        **m -= n * 2;
+    }
+}
+
+#[entity_command]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut set: ParamSet<(Query<&mut TestUsize>, &World)>) {
+    let len = set.p1().entities().len() as usize;
+    assert_eq!(len, 1);
+    **set.p0().get_mut(entity).unwrap() -= n * len;
+}
+
+#[command]
+fn baz(In(n): In<usize>, world: &World, mut count: Local<usize>) {
+    *count += n * world.entities().len() as usize;
+    assert_eq!(*count, 10);
+}
+
+/// `ParamSet`s are system params, and should not become fields of our command
+#[test]
+fn param_set() {
+    let mut world = World::new();
+    world.spawn((TestUsize(20), Marker));
+    world.spawn(TestUsize(40));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5).add(FooCommand { n: 5 });
+
+    queue.apply(&mut world);
+
+    world.foo(5).foo(5);
+
+    let mut query = world.query::<&TestUsize>();
+    assert!(query.iter(&world).all(|m| **m == 0));
+}
+
+/// `ParamSet`s containing a `&World` should not be mistaken for an exclusive command
+#[test]
+fn param_set_world() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).bar(5);
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).bar(5);
+
+    assert_eq!(**world.query::<&TestUsize>().single(&world), 10);
+}
+
+/// A shared `&World` is a read-only system param rather than an exclusive command
+#[test]
+fn shared_world() {
+    let mut world = World::new();
+    world.spawn_empty();
+    world.spawn_empty();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.baz(5);
+
+    queue.apply(&mut world);
+
+    world.baz(5);
+}
@@ -0,0 +1,80 @@
+use bevy::ecs::system::{Command, CommandQueue};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Clone, Copy)]
+struct Pair {
+    a: usize,
+    b: usize,
+}
+
+#[command]
+fn foo(world: &mut World, mut n: usize, Pair { a, b }: Pair) -> &mut Self {
+    n += a * b;
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command]
+fn bar(world: &mut World, (a, b): (usize, usize), pair: usize, Pair { a: c, .. }: Pair) {
+    **world.resource_mut::<TestUsize>() -= a + b + pair + c;
+}
+
+#[entity_command]
+fn baz(entity: Entity, world: &mut World, Pair { a, b }: Pair) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= a + b;
+}
+
+#[entity_command(closure)]
+fn qux(entity: Entity, world: &mut World, Pair { a, b }: Pair) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= a + b;
+}
+
+/// `mut` bindings and destructured params work unchanged, becoming fields named after their type
+#[test]
+fn patterns() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(31));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(1, Pair { a: 2, b: 2 }).add(FooCommand {
+        n: 1,
+        pair: Pair { a: 1, b: 3 },
+    });
+
+    queue.apply(&mut world);
+
+    world.foo(1, Pair { a: 1, b: 1 });
+    world.bar((1, 2), 3, Pair { a: 4, b: 0 });
+
+    assert_eq!(**world.resource::<TestUsize>(), 10);
+
+    // `pair` is already taken by a param, so the last field falls back to its position
+    BarCommand {
+        arg1: (1, 2),
+        pair: 3,
+        arg3: Pair { a: 4, b: 0 },
+    }
+    .apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Destructured params work for entity commands, including `closure` ones
+#[test]
+fn patterns_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(10)).id();
+
+    world.entity_mut(entity).baz(Pair { a: 2, b: 3 });
+    BazEntityCommand::new(Pair { a: 1, b: 1 })
+        .with_entity(entity)
+        .apply(&mut world);
+    world.entity_mut(entity).qux(Pair { a: 1, b: 2 });
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
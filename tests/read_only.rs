@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(read_only)]
+fn total(world: &World) -> usize {
+    **world.resource::<TestUsize>()
+}
+
+#[command(read_only)]
+/// Read-only commands may borrow from the world they are called against
+fn get_or<'a>(world: &'a World, default: &'a TestUsize) -> &'a TestUsize {
+    world.get_resource::<TestUsize>().unwrap_or(default)
+}
+
+#[command(read_only, name = "validate")]
+#[must_use = "validation does nothing by itself"]
+fn check((min, max): (usize, usize), world: &World) -> bool {
+    (min..max).contains(&(world.entities().len() as usize))
+}
+
+/// Read-only commands are called immediately against the world, returning their value
+#[test]
+fn read_only() {
+    let mut world = World::new();
+    world.spawn_empty();
+
+    let default = TestUsize(5);
+    assert_eq!(**world.get_or(&default), 5);
+
+    world.insert_resource(TestUsize(10));
+
+    assert_eq!(world.total(), 10);
+    assert_eq!(WorldTotalExt::total(&world), 10);
+    assert_eq!(**world.get_or(&default), 10);
+
+    let world = &world;
+    assert!(world.validate((0, 2)));
+    assert!(!world.validate((2, 4)));
+}
@@ -27,7 +27,7 @@ fn renamed_trait() {
     world.insert_resource(TestUsize(10));
 
     let mut queue = CommandQueue::default();
-    let mut commands = Commands::new(&mut queue, &mut world);
+    let mut commands = Commands::new(&mut queue, &world);
 
     // Call via Commands
     FooExt::add(&mut commands, 10);
@@ -47,7 +47,7 @@ fn renamed_entity_trait() {
     let entity = world.spawn(TestUsize(30)).id();
 
     let mut queue = CommandQueue::default();
-    let mut commands = Commands::new(&mut queue, &mut world);
+    let mut commands = Commands::new(&mut queue, &world);
 
     // Call via Commands
     BarExt::do_sub(&mut commands.entity(entity), 10);
@@ -63,3 +63,25 @@ fn renamed_entity_trait() {
 
     assert_eq!(**world.query::<&TestUsize>().single(&world), 0);
 }
+
+#[command(method = reset, trait_name = UiResetExt)]
+fn reset_ui(world: &mut World) {
+    **world.resource_mut::<TestUsize>() += 1;
+}
+
+#[command(method = reset, trait_name = AudioResetExt)]
+fn reset_audio(world: &mut World) {
+    **world.resource_mut::<TestUsize>() += 10;
+}
+
+/// Commands with different traits may share a method, called through their trait
+#[test]
+fn shared_method() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(0));
+
+    UiResetExt::reset(&mut world);
+    AudioResetExt::reset(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 11);
+}
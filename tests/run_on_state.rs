@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum Level {
+    #[default]
+    Menu,
+    One,
+    Two,
+}
+
+#[command(run_on_enter, run_on_exit)]
+fn drain(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(run_on_enter, closure)]
+fn fill(In(n): In<usize>, mut value: ResMut<TestUsize>) {
+    **value += n;
+}
+
+/// `run_on_enter` and `run_on_exit` queue the command each time a state is entered or exited
+#[test]
+fn run_on_state() {
+    let mut app = App::new();
+    app.init_state::<Level>().insert_resource(TestUsize(100));
+
+    app.queue_drain_on_enter(Level::One, 10)
+        .queue_drain_on_exit(Level::One, 5)
+        .queue_fill_on_enter(Level::Two, 1)
+        .queue_drain(20);
+
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 80);
+
+    for level in [Level::One, Level::Two, Level::One, Level::Menu] {
+        app.world.resource_mut::<NextState<Level>>().set(level);
+        app.update();
+    }
+
+    assert_eq!(**app.world.resource::<TestUsize>(), 51);
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    #[default]
+    InGame,
+    Menu,
+}
+
+#[command(budget_ms = 1000, scoped_to_state = GameState::InGame)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+/// Buffered commands are dropped when their state exits, rather than drained afterwards
+#[test]
+fn scoped_to_state() {
+    let mut app = App::new();
+    app.insert_resource(TestUsize(20))
+        .init_state::<GameState>()
+        .add_plugins(FooStateScopePlugin)
+        .add_systems(Update, foo_command_drain);
+
+    app.world.foo(5);
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 15);
+
+    // leaving the state happens before `Update` runs the drain
+    app.world.foo(5).foo(5);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Menu);
+    app.update();
+    app.update();
+    assert_eq!(**app.world.resource::<TestUsize>(), 15);
+}
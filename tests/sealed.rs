@@ -0,0 +1,44 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(sealed)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(sealed, commands)]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+#[command(sealed, read_only)]
+fn total(world: &World) -> usize {
+    **world.resource::<TestUsize>()
+}
+
+/// Sealed traits behave like any other for the types they are implemented for
+#[test]
+fn sealed() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(5);
+    commands.entity(entity).bar(5);
+    commands.bar(entity, 5);
+
+    queue.apply(&mut world);
+
+    world.foo(5);
+    world.entity_mut(entity).bar(5);
+
+    assert_eq!(world.total(), 10);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 5);
+}
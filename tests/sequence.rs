@@ -0,0 +1,68 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Resource, Default)]
+struct Log(Vec<&'static str>);
+
+#[command]
+fn foo(world: &mut World, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+    world.resource_mut::<Log>().0.push("foo");
+}
+
+#[entity_command]
+fn bar(
+    In((entity, n)): In<(Entity, usize)>,
+    mut query: Query<&mut TestUsize>,
+    mut log: ResMut<Log>,
+) {
+    **query.get_mut(entity).unwrap() -= n;
+    log.0.push("bar");
+}
+
+/// Takes `n` from the resource and twice from `target`
+#[command_sequence]
+fn foo_then_bar(target: Entity, n: usize) -> &mut Self {
+    foo(n);
+    target.bar(n * 2);
+    foo(1);
+}
+
+/// Sequences apply their steps in order, mapping their params onto each step's args
+#[test]
+fn sequence() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    world.init_resource::<Log>();
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo_then_bar(entity, 2).foo_then_bar(entity, 3);
+
+    // nothing is applied until the queue is
+    assert!(world.resource::<Log>().0.is_empty());
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 13);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+    assert_eq!(
+        world.resource::<Log>().0,
+        ["foo", "bar", "foo", "foo", "bar", "foo"]
+    );
+
+    world.foo_then_bar(entity, 5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 7);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+    assert_eq!(
+        FooThenBarCommand::DOC,
+        "Takes `n` from the resource and twice from `target`"
+    );
+}
@@ -0,0 +1,50 @@
+// most tests here are generated by the `test` attribute, as `<struct_name>_smoke_test`
+use bevy::prelude::*;
+use bevy_commandify::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Component)]
+struct Marker;
+
+#[command(test)]
+fn foo(world: &mut World, n: usize, name: String) {
+    world.spawn_empty();
+    assert_eq!(n, 0);
+    assert!(name.is_empty());
+}
+
+#[command(test, closure)]
+fn bar(In(n): In<usize>, mut commands: Commands) {
+    assert_eq!(n, 0);
+    commands.spawn_empty();
+}
+
+#[entity_command(test)]
+fn baz(In((entity, n)): In<(Entity, u32)>, mut commands: Commands) {
+    assert_eq!(n, 0);
+    commands.entity(entity).insert(Marker);
+}
+
+static DRAINED: AtomicUsize = AtomicUsize::new(0);
+
+#[command(test, budget_ms = 1000)]
+fn qux(world: &mut World, n: usize) {
+    world.spawn_empty();
+    DRAINED.fetch_add(n + 1, Ordering::SeqCst);
+}
+
+#[entity_command(test, apply_in_fixed)]
+fn quux(entity: Entity, world: &mut World) {
+    world.entity_mut(entity).insert(Marker);
+    DRAINED.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Smoke tests of buffered commands drain them, so their bodies run
+#[test]
+fn smoke_test_drains() {
+    // the generated tests may also be running, which only adds to the count
+    let before = DRAINED.load(Ordering::SeqCst);
+    qux_command_smoke_test();
+    quux_entity_command_smoke_test();
+    assert!(DRAINED.load(Ordering::SeqCst) >= before + 2);
+}
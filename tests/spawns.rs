@@ -0,0 +1,43 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Component)]
+struct Enemy;
+
+#[entity_command(spawns)]
+fn spawn_enemy(world: &mut World, entity: Entity, health: usize) {
+    world.entity_mut(entity).insert((Enemy, TestUsize(health)));
+}
+
+#[entity_command(spawns, all)]
+fn spawn_named(In((entity, name)): In<(Entity, String)>, mut commands: Commands) {
+    commands.entity(entity).insert(Name::new(name));
+}
+
+/// `spawns` generates a method spawning the target, returning it for further chaining
+#[test]
+fn spawns() {
+    let mut world = World::new();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    let first = commands.spawn_enemy(10).insert(Name::new("first")).id();
+    let second = commands.spawn_named("second".into()).id();
+    commands.spawn_named_all([first], "renamed".into());
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.get::<TestUsize>(first).unwrap(), 10);
+    assert_eq!(world.get::<Name>(first).unwrap().as_str(), "renamed");
+    assert_eq!(world.get::<Name>(second).unwrap().as_str(), "second");
+
+    let third = world.spawn_enemy(20).insert(Name::new("third")).id();
+
+    assert_eq!(**world.get::<TestUsize>(third).unwrap(), 20);
+    assert_eq!(world.query::<&Enemy>().iter(&world).count(), 2);
+}
@@ -0,0 +1,67 @@
+use bevy::ecs::system::{CommandQueue, StaticSystemParam, SystemParam};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command]
+fn foo<P: SystemParam + 'static>(
+    _param: StaticSystemParam<P>,
+    mut m: ResMut<TestUsize>,
+) -> &mut Self {
+    **m -= 10;
+}
+
+#[command]
+fn bar<P: SystemParam + 'static>(
+    In(n): In<usize>,
+    _param: StaticSystemParam<P>,
+    mut m: ResMut<TestUsize>,
+) -> &mut Self {
+    **m -= n;
+}
+
+#[entity_command]
+fn baz<P>(In(entity): In<Entity>, _param: StaticSystemParam<P>, mut query: Query<&mut TestUsize>)
+where
+    P: SystemParam + 'static,
+{
+    **query.get_mut(entity).unwrap() -= 10;
+}
+
+/// Generic `SystemParam`s should be usable via `StaticSystemParam` in commands
+#[test]
+fn static_param() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(30));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo::<Local<usize>>().bar::<()>(5);
+
+    queue.apply(&mut world);
+
+    world.foo::<()>().bar::<Query<Entity>>(5);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Bounds declared in a `where` clause should carry over to the generated items
+#[test]
+fn static_param_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(entity).baz::<Local<usize>>();
+
+    queue.apply(&mut world);
+
+    world.entity_mut(entity).baz::<()>();
+
+    assert_eq!(**world.query::<&TestUsize>().single(&world), 0);
+}
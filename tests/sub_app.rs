@@ -0,0 +1,33 @@
+use bevy::app::SubApp;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(sub_app)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(sub_app)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+#[command(read_only, sub_app)]
+fn baz(world: &World) -> usize {
+    **world.resource::<TestUsize>()
+}
+
+/// `sub_app` commands are callable on a `SubApp`, applying to its world
+#[test]
+fn sub_app() {
+    let mut sub_app = SubApp::new(App::new(), |_, _| {});
+    sub_app.app.insert_resource(TestUsize(20));
+
+    sub_app.foo(5).foo(5);
+    sub_app.bar(5);
+
+    assert_eq!(sub_app.baz(), 5);
+}
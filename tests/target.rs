@@ -0,0 +1,36 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command]
+/// Moves `n` from the `source` entity to the target
+fn absorb(world: &mut World, source: Entity, #[target] dest: Entity, n: usize) -> &mut Self {
+    **world.get_mut::<TestUsize>(source).unwrap() -= n;
+    **world.get_mut::<TestUsize>(dest).unwrap() += n;
+}
+
+/// The `#[target]` entity is the one the command is issued against, the others become fields
+#[test]
+fn target() {
+    let mut world = World::new();
+    let source = world.spawn(TestUsize(30)).id();
+    let dest = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.entity(dest).absorb(source, 10).absorb(source, 5);
+    commands
+        .entity(dest)
+        .add(AbsorbEntityCommand { source, n: 5 });
+
+    queue.apply(&mut world);
+
+    world.entity_mut(dest).absorb(source, 10);
+
+    assert_eq!(**world.get::<TestUsize>(source).unwrap(), 0);
+    assert_eq!(**world.get::<TestUsize>(dest).unwrap(), 30);
+}
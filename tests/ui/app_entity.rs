@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[entity_command(app)]
+fn foo(world: &mut World, entity: Entity) { }
+
+/// Test that `app` only applies to commands
+fn main() { }
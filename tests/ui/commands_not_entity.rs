@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[command(commands)]
+fn foo(world: &mut World) { }
+
+/// Test that `commands` only applies to entity commands
+fn main() { }
@@ -0,0 +1,11 @@
+use bevy_commandify::*;
+use bevy::prelude::*;
+
+#[derive(Component)]
+struct Foo;
+
+#[entity_command]
+fn foo(In(entity): In<Entity>, item: Item<&mut Foo>) { }
+
+/// Test that items can't be fetched for a system command
+fn main() { }
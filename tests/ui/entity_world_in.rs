@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[entity_command]
+fn foo(entity: EntityWorldMut, In(n): In<usize>) { }
+
+/// Test that `EntityWorldMut` commands reject `In` params, pointing at the `In`
+fn main() { }
@@ -0,0 +1,15 @@
+mod library {
+    use bevy::prelude::*;
+    use bevy_commandify::*;
+
+    #[command(fn_vis = "")]
+    pub fn foo(world: &mut World) {
+        world.clear_entities();
+    }
+}
+
+/// Test that `fn_vis` hides the fn while its command stays public
+fn main() {
+    let _ = library::FooCommand;
+    library::foo(&mut bevy::prelude::World::new());
+}
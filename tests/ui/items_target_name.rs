@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[entity_command]
+fn foo(_: Entity, transform: Item<&mut Transform>) { }
+
+/// Test that entity items need a named target, pointing at the target
+fn main() { }
@@ -0,0 +1,11 @@
+use bevy_commandify::*;
+use bevy::prelude::*;
+
+#[command(name = "bar", method = "baz")]
+fn foo(_world: &mut World, _n: usize) { }
+
+/// Test that generated methods point back at the original fn
+fn main() {
+    let mut world = World::new();
+    world.baz();
+}
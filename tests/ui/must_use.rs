@@ -0,0 +1,13 @@
+#![deny(unused_must_use)]
+
+use bevy_commandify::*;
+use bevy::prelude::*;
+
+#[command(read_only)]
+fn foo(world: &World) -> usize { world.entities().len() as usize }
+
+/// Test that the results of read-only commands must be used
+fn main() {
+    let world = World::new();
+    world.foo();
+}
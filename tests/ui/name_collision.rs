@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[entity_command(both, struct_name = "FooCommand")]
+fn foo(world: &mut World, entity: Entity) { }
+
+/// Test that generated items with the same name are caught
+fn main() { }
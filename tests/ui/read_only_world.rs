@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[command(read_only)]
+fn foo(n: usize) -> usize { n }
+
+/// Test that read-only commands require a `&World`
+fn main() { }
@@ -0,0 +1,14 @@
+use bevy_commandify::*;
+use bevy::prelude::*;
+
+#[command(sealed)]
+fn foo(world: &mut World) { }
+
+struct Bar;
+
+impl CommandsFooExt for Bar {
+    fn foo(&mut self) { }
+}
+
+/// Test that sealed traits cannot be implemented for other types
+fn main() { }
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+#[command]
+fn foo(world: &mut World, n: usize) {
+    world.clear_entities();
+    let _ = n;
+}
+
+#[command_sequence]
+fn bar(n: usize) {
+    let m = n + 1;
+    foo(m);
+}
+
+#[command_sequence]
+fn baz(n: usize) {
+    self::foo(n);
+}
+
+#[command_sequence]
+fn qux(world: usize) {
+    foo(world);
+}
+
+/// Test that sequence steps must be calls to commands in scope, and the world isn't shadowed
+fn main() { }
@@ -0,0 +1,7 @@
+use bevy_commandify::*;
+
+#[entity_command]
+fn foo(world: &mut World, entity: Entity, #[target] n: usize) { }
+
+/// Test that `#[target]` must mark an entity
+fn main() { }
@@ -0,0 +1,11 @@
+use bevy_commandify::*;
+
+#[commandify_trait(delegate = Combat)]
+trait CombatApi {
+    fn attack(&self, world: &mut bevy::prelude::World, damage: usize);
+}
+
+struct Combat;
+
+/// Test that trait methods cannot take `self`
+fn main() { }
@@ -0,0 +1,15 @@
+mod library {
+    use bevy::prelude::*;
+    use bevy_commandify::*;
+
+    #[command(trait_vis = "pub(self)")]
+    pub fn focus(world: &mut World, target: Option<Entity>) {
+        world.clear_entities();
+        let _ = target;
+    }
+}
+
+use library::EntityCommandsFocusExt;
+
+/// Test that `trait_vis` applies to the `EntityCommands` trait of `Option<Entity>` commands
+fn main() { }
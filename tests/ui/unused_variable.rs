@@ -0,0 +1,10 @@
+#![deny(unused_variables)]
+
+use bevy_commandify::*;
+use bevy::prelude::*;
+
+#[command]
+fn foo(world: &mut World, n: usize) { }
+
+/// Test that genuine warnings in command bodies are no longer suppressed
+fn main() { }
@@ -0,0 +1,17 @@
+#![deny(deprecated)]
+
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+#[command(no_trait, trait_name = "FooExt")]
+fn foo(world: &mut World) {
+    world.clear_entities();
+}
+
+#[command(no_trait, no_world, method = "reset")]
+fn bar(world: &mut World) {
+    world.clear_entities();
+}
+
+/// Test that args without any effect are warned about
+fn main() { }
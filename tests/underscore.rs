@@ -0,0 +1,80 @@
+use bevy::ecs::system::{CommandQueue, StaticSystemParam, SystemParam};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[derive(Clone, Copy)]
+struct Marker;
+
+#[command]
+fn foo(world: &mut World, _unused: Marker, _: Marker, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command]
+fn bar(world: &mut World, _n: usize, n: usize) {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command]
+fn baz<P: SystemParam + 'static>(
+    In((_marker, n)): In<(Marker, usize)>,
+    _param: StaticSystemParam<P>,
+    mut m: ResMut<TestUsize>,
+) {
+    **m -= n;
+}
+
+/// Underscored params become fields without their leading underscores, and `_` is named after its type
+#[test]
+fn underscore() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.foo(Marker, Marker, 5).add(FooCommand {
+        unused: Marker,
+        marker: Marker,
+        n: 5,
+    });
+
+    queue.apply(&mut world);
+
+    world.foo(Marker, Marker, 5);
+
+    // `n` is taken, so `_n` keeps its name
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.add(BarCommand { _n: 0, n: 5 });
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// Underscored inputs don't clash with the marker field of generic commands
+#[test]
+fn underscore_marker() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(10));
+
+    world.baz::<()>(Marker, 5);
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.add(BazCommand::<()> {
+        marker: Marker,
+        n: 5,
+        _marker: Default::default(),
+    });
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
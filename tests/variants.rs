@@ -0,0 +1,38 @@
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[command(variants(small = (5), large = 50))]
+fn drain(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[entity_command(variants(potion = (5, 2), elixir = (20, 1)))]
+fn heal(In((entity, n, times)): In<(Entity, usize, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() += n * times;
+}
+
+/// `variants` generate methods queueing the command with preset fields
+#[test]
+fn variants() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(100));
+    let entity = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.drain_small().drain_large();
+    commands.entity(entity).heal_potion();
+
+    queue.apply(&mut world);
+
+    world.drain_small().drain(1);
+    world.entity_mut(entity).heal_elixir();
+
+    assert_eq!(**world.resource::<TestUsize>(), 39);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 30);
+}
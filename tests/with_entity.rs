@@ -0,0 +1,59 @@
+use bevy::ecs::system::{Command, CommandQueue};
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+#[entity_command]
+fn foo(entity: Entity, world: &mut World, n: usize) {
+    **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+}
+
+#[entity_command]
+fn bar(In((entity, n)): In<(Entity, usize)>, mut query: Query<&mut TestUsize>) {
+    **query.get_mut(entity).unwrap() -= n;
+}
+
+/// Entity command structs may be bound to an entity up front, becoming plain commands
+#[test]
+fn with_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(30)).id();
+
+    let bound = vec![
+        FooEntityCommand::new(5).with_entity(entity),
+        FooEntityCommand::new(5).with_entity(entity),
+    ];
+    for command in bound {
+        command.apply(&mut world);
+    }
+    BarEntityCommand::new(10)
+        .with_entity(entity)
+        .apply(&mut world);
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.add(FooEntityCommand::new(5).with_entity(entity));
+    commands.add(BarEntityCommand::new(5).with_entity(entity));
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
+
+/// Entity command structs may be built in `const` items, such as tables of pre-built commands
+#[test]
+fn const_new() {
+    const TABLE: [FooEntityCommand; 2] = [FooEntityCommand::new(10), FooEntityCommand::new(20)];
+
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(30)).id();
+
+    for command in TABLE {
+        command.with_entity(entity).apply(&mut world);
+    }
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
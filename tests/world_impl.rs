@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+/// A world-like context, such as those handed to render-world plugins
+struct Context {
+    world: World,
+}
+
+impl AsMut<World> for Context {
+    fn as_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+}
+
+impl AsRef<World> for Context {
+    fn as_ref(&self) -> &World {
+        &self.world
+    }
+}
+
+#[command(world_impl = Context)]
+fn foo(world: &mut World, n: usize) -> &mut Self {
+    **world.resource_mut::<TestUsize>() -= n;
+}
+
+#[command(world_impl = Context)]
+fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+    **m -= n;
+}
+
+#[command(read_only, world_impl = Context)]
+fn baz(world: &World) -> usize {
+    **world.resource::<TestUsize>()
+}
+
+/// `world_impl` types are callable like the world they wrap
+#[test]
+fn world_impl() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(20));
+    let mut context = Context { world };
+
+    context.foo(5).foo(5);
+    context.bar(5);
+
+    assert_eq!(context.baz(), 5);
+}
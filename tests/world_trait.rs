@@ -0,0 +1,106 @@
+#![deny(private_interfaces, private_bounds)]
+
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use bevy_commandify::*;
+
+mod common;
+use common::TestUsize;
+
+mod engine {
+    use super::*;
+
+    #[command(world_trait_name = "WorldFooExt", trait_vis = "pub(crate)")]
+    pub fn foo(world: &mut World, n: usize) -> &mut Self {
+        **world.resource_mut::<TestUsize>() -= n;
+    }
+
+    #[command(world_trait_name = WorldBarExt, no_trait)]
+    pub fn bar(In(n): In<usize>, mut m: ResMut<TestUsize>) {
+        **m -= n;
+    }
+
+    #[command(trait_vis = "pub(super)")]
+    fn grow(world: &mut World, target: Option<Entity>, n: usize) {
+        match target {
+            Some(entity) => **world.get_mut::<TestUsize>(entity).unwrap() += n,
+            None => **world.resource_mut::<TestUsize>() += n,
+        }
+    }
+
+    #[entity_command(world_trait_name = "EntityWorldBazExt", world_trait_vis = "pub(super)")]
+    pub fn baz(world: &mut World, entity: Entity, n: usize) {
+        **world.get_mut::<TestUsize>(entity).unwrap() -= n;
+    }
+}
+
+/// `world_trait_name` moves the `World` method onto its own trait
+#[test]
+fn world_trait() {
+    let mut world = World::new();
+    world.insert_resource(TestUsize(40));
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    {
+        use engine::CommandsFooExt;
+        commands.foo(5).foo(5);
+    }
+
+    queue.apply(&mut world);
+
+    {
+        use engine::{WorldBarExt, WorldFooExt};
+        world.foo(10).foo(10);
+        world.bar(0);
+        world.bar(10);
+    }
+
+    assert_eq!(**world.resource::<TestUsize>(), 0);
+}
+
+/// `world_trait_name` moves the `EntityWorldMut` method onto its own trait
+#[test]
+fn world_trait_entity() {
+    let mut world = World::new();
+    let entity = world.spawn(TestUsize(20)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    {
+        use engine::EntityCommandsBazExt;
+        commands.entity(entity).baz(10);
+    }
+
+    queue.apply(&mut world);
+
+    {
+        use engine::EntityWorldBazExt;
+        world.entity_mut(entity).baz(10);
+    }
+
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 0);
+}
+
+/// `trait_vis` also applies to the traits issuing `Option<Entity>` commands from `Commands` and `EntityCommands`
+#[test]
+fn world_trait_optional_entity() {
+    use engine::{CommandsGrowExt, EntityCommandsGrowExt};
+
+    let mut world = World::new();
+    world.insert_resource(TestUsize(0));
+    let entity = world.spawn(TestUsize(0)).id();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, &world);
+
+    commands.grow(None, 5);
+    commands.entity(entity).grow(10);
+
+    queue.apply(&mut world);
+
+    assert_eq!(**world.resource::<TestUsize>(), 5);
+    assert_eq!(**world.get::<TestUsize>(entity).unwrap(), 10);
+}